@@ -0,0 +1,226 @@
+use crate::request::{EndPoint, RequestBuilder, Vocabulary};
+use crate::response::WordElement;
+use crate::{DatamuseClient, Result};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// A wrapper around a [DatamuseClient](DatamuseClient) which caches the word lists of the
+/// queries it sends in memory. Sending a query whose url is identical to a cached one returns the
+/// cached word list without sending a request, which saves the daily request limit. Once the
+/// capacity is reached, the least recently used word list is removed from the cache
+#[derive(Debug)]
+pub struct CachingDatamuseClient {
+    client: DatamuseClient,
+    capacity: usize,
+    cache: Mutex<LruCache<Vec<WordElement>>>,
+}
+
+//A map which removes the least recently used entry once it holds more entries than its capacity
+#[derive(Debug)]
+pub(crate) struct LruCache<V> {
+    capacity: usize,
+    entries: HashMap<String, V>,
+    order: VecDeque<String>, //From least to most recently used
+}
+
+impl CachingDatamuseClient {
+    /// Returns a new CachingDatamuseClient which sends its requests with the given client and
+    /// keeps the word lists of at most capacity different queries. A capacity of 0 disables caching
+    pub fn new(client: DatamuseClient, capacity: usize) -> Self {
+        CachingDatamuseClient {
+            client,
+            capacity,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Returns a new [RequestBuilder](RequestBuilder) of the underlying client, see
+    /// [DatamuseClient::new_query()](DatamuseClient::new_query). The query can be sent with
+    /// [list()](CachingDatamuseClient::list) to make use of the cache
+    pub fn new_query(&self, vocabulary: Vocabulary, endpoint: EndPoint) -> RequestBuilder<'_> {
+        self.client.new_query(vocabulary, endpoint)
+    }
+
+    /// Returns the underlying client
+    pub fn client(&self) -> &DatamuseClient {
+        &self.client
+    }
+
+    /// Returns the word list of the given query like [RequestBuilder::list()](RequestBuilder::list).
+    /// If a query with the same url is cached, its word list is returned without sending a request.
+    /// Otherwise the request is sent and its word list is added to the cache. Filters which are not
+    /// part of the url (such as [unusual_words_only()](RequestBuilder::unusual_words_only)) are
+    /// applied after reading from the cache
+    pub async fn list(&self, request: &RequestBuilder<'_>) -> Result<Vec<WordElement>> {
+        let built = request.build()?;
+        let key = built.url().to_string();
+
+        if let Some(word_list) = self.cache.lock().unwrap().get(&key).cloned() {
+            return Ok(request.apply_filters(word_list));
+        }
+
+        let word_list = built.send().await?.list()?;
+        self.cache.lock().unwrap().insert(key, word_list.clone());
+
+        Ok(request.apply_filters(word_list))
+    }
+
+    /// Returns the highest number of queries whose word lists are cached
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of queries whose word lists are currently cached
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().len()
+    }
+
+    /// Returns whether no word lists are currently cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all word lists from the cache
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+impl<V> LruCache<V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn get(&mut self, key: &str) -> Option<&V> {
+        if !self.entries.contains_key(key) {
+            return None;
+        }
+        self.touch(key);
+
+        self.entries.get(key)
+    }
+
+    pub(crate) fn insert(&mut self, key: String, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.insert(key.clone(), value).is_some() {
+            self.touch(&key);
+            return;
+        }
+
+        self.order.push_back(key);
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) {
+        if self.entries.remove(key).is_some() {
+            self.order.retain(|val| val != key);
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    //Marks the key as the most recently used
+    fn touch(&mut self, key: &str) {
+        if let Some(index) = self.order.iter().position(|val| val == key) {
+            if let Some(key) = self.order.remove(index) {
+                self.order.push_back(key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CachingDatamuseClient, LruCache};
+    use crate::test_server::{MockResponse, MockServer};
+    use crate::{EndPoint, Vocabulary};
+
+    #[tokio::test]
+    async fn identical_queries() {
+        let server = MockServer::routes(vec![
+            ("ml=cow", MockResponse::json(r#"[{"word":"milk"}]"#)),
+            ("ml=dog", MockResponse::json(r#"[{"word":"bark"}]"#)),
+        ])
+        .await;
+        let client = CachingDatamuseClient::new(server.client(), 10);
+
+        for _ in 0..3 {
+            let request = client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like("cow");
+            assert_eq!("milk", client.list(&request).await.unwrap()[0].word);
+        }
+        assert_eq!(1, server.hits());
+
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dog");
+        assert_eq!("bark", client.list(&request).await.unwrap()[0].word);
+        assert_eq!(2, server.hits());
+        assert_eq!(2, client.len());
+
+        client.clear();
+        assert!(client.is_empty());
+        client.list(&request).await.unwrap();
+        assert_eq!(3, server.hits());
+    }
+
+    #[tokio::test]
+    async fn least_recently_used() {
+        let server = MockServer::start(vec![MockResponse::json(r#"[{"word":"word"}]"#)]).await;
+        let client = CachingDatamuseClient::new(server.client(), 2);
+        let query = |word: &str| {
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like(word)
+        };
+
+        client.list(&query("a")).await.unwrap();
+        client.list(&query("b")).await.unwrap();
+        client.list(&query("a")).await.unwrap(); //"b" is now the least recently used
+        client.list(&query("c")).await.unwrap();
+        assert_eq!(3, server.hits());
+        assert_eq!(2, client.len());
+
+        client.list(&query("a")).await.unwrap();
+        assert_eq!(3, server.hits());
+        client.list(&query("b")).await.unwrap();
+        assert_eq!(4, server.hits());
+        assert_eq!(2, client.capacity());
+    }
+
+    #[test]
+    fn lru_cache() {
+        let mut cache = LruCache::new(2);
+        cache.insert(String::from("a"), 1);
+        cache.insert(String::from("b"), 2);
+        assert_eq!(Some(&1), cache.get("a"));
+
+        cache.insert(String::from("c"), 3);
+        assert_eq!(None, cache.get("b"));
+        assert_eq!(2, cache.len());
+
+        cache.remove("a");
+        assert_eq!(None, cache.get("a"));
+        assert_eq!(Some(&3), cache.get("c"));
+        assert_eq!(1, cache.len());
+    }
+}
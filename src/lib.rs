@@ -68,29 +68,106 @@ extern crate reqwest;
 extern crate serde;
 extern crate serde_json;
 
+use reqwest::header::{HeaderName, ACCEPT_LANGUAGE, USER_AGENT};
 use std::error;
 use std::fmt::{self, Display, Formatter};
 use std::result;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use tokio::time::Instant;
 
+mod cache;
+mod queries;
 mod request;
 mod response;
+#[cfg(test)]
+mod test_server;
+mod trie;
+mod word_list;
 
+pub use cache::*;
 pub use request::*;
 pub use response::*;
+pub use trie::*;
+pub use word_list::*;
 
 /// This struct represents the client which can be used to make requests
 /// to the Datamuse api. Requests can be created using the new_query() method
 #[derive(Debug)]
 pub struct DatamuseClient {
     client: reqwest::Client,
+    base_url: String,
+    headers: Vec<(HeaderName, String)>, //Added to every request when it is built
+    max_response_bytes: Option<usize>,
+    normalization: NormalizationRules,
+    require_https: bool,
+    requests_sent: AtomicU64,
+    definition_cache: Mutex<LruCache<(Instant, Vec<Definition>)>>, //Used by define_cached()
 }
 
+/// The highest number of words whose definitions are cached by
+/// [define_cached()](DatamuseClient::define_cached). Once it is reached, the definitions of the
+/// least recently looked up word are removed
+pub const DEFINITION_CACHE_CAPACITY: usize = 1000;
+
+/// The number of requests per day which can be made to the Datamuse api before
+/// requests may be rate-limited
+pub const DAILY_REQUEST_LIMIT: u64 = 100_000;
+
 impl DatamuseClient {
-    /// Returns a new DatamuseClient struct
+    /// Returns a new DatamuseClient struct. Its requests ask for gzip compressed responses,
+    /// which are decompressed automatically
     pub fn new() -> Self {
+        DatamuseClient::with_client(reqwest::Client::new())
+    }
+
+    /// Returns a new DatamuseClient struct which sends its requests with the given reqwest client.
+    /// This allows an already configured client (e.g. with a proxy or custom tls settings) and its
+    /// connection pool to be reused
+    pub fn with_client(client: reqwest::Client) -> Self {
         DatamuseClient {
-            client: reqwest::Client::new(),
+            client,
+            base_url: String::from("https://api.datamuse.com/"),
+            headers: Vec::new(),
+            max_response_bytes: None,
+            normalization: NormalizationRules::default(),
+            require_https: true,
+            requests_sent: AtomicU64::new(0),
+            definition_cache: Mutex::new(LruCache::new(DEFINITION_CACHE_CAPACITY)),
+        }
+    }
+
+    /// Sets the base url to which the endpoint paths ("words" or "sug") are appended when building
+    /// requests. This allows requests to be sent to a mock server for testing or to a self-hosted
+    /// deployment of the api. A trailing '/' is added if it is missing. By default the official
+    /// api at "https://api.datamuse.com/" is used. Note that urls which do not use https are
+    /// rejected unless [require_https()](DatamuseClient::require_https) is disabled
+    pub fn with_base_url(mut self, base: &str) -> Self {
+        self.base_url = String::from(base);
+        if !self.base_url.ends_with('/') {
+            self.base_url.push('/');
         }
+
+        self
+    }
+
+    /// Sets the Accept-Language header (e.g. "es") on all requests sent by this client.
+    /// This may influence the language of the definitions returned for the Spanish vocabulary.
+    /// If the given value is not a valid header value, building requests will return an error
+    pub fn with_accept_language(mut self, lang: &str) -> Self {
+        self.set_header(ACCEPT_LANGUAGE, lang);
+
+        self
+    }
+
+    /// Sets the User-Agent header on all requests sent by this client in place of the default
+    /// of reqwest. Datamuse asks heavy users to identify themselves, so it is recommended to include
+    /// the name of the application and contact information (e.g. "my-app/1.0 (me@example.com)").
+    /// If the given value is not a valid header value, building requests will return an error
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.set_header(USER_AGENT, user_agent);
+
+        self
     }
 
     /// Returns a new [RequestBuilder](request::RequestBuilder) struct with which requests can be created
@@ -103,6 +180,79 @@ impl DatamuseClient {
     ) -> RequestBuilder<'a> {
         RequestBuilder::new(self, vocabulary, endpoint)
     }
+
+    /// Returns a new [RequestBuilder](request::RequestBuilder) with all of the parameters set in the
+    /// given [QueryConfig](request::QueryConfig) applied. This allows queries to be built from
+    /// configuration files as the config can be deserialized with serde
+    pub fn query_from_config(&self, config: QueryConfig) -> RequestBuilder<'_> {
+        RequestBuilder::from_config(self, config)
+    }
+
+    /// Returns how many more requests can be sent today before reaching the
+    /// [DAILY_REQUEST_LIMIT](DAILY_REQUEST_LIMIT). Note that this only counts the requests
+    /// sent by this client since it was created and not those of other clients or programs
+    pub fn remaining_budget(&self) -> u64 {
+        DAILY_REQUEST_LIMIT.saturating_sub(self.requests_sent.load(Ordering::Relaxed))
+    }
+
+    /// Limits the size of the response bodies read by this client to the given number of bytes.
+    /// Sending a request whose response is larger returns an
+    /// [Error::ResponseTooLarge](Error::ResponseTooLarge) instead. By default there is no limit
+    pub fn with_max_response_bytes(mut self, limit: usize) -> Self {
+        self.max_response_bytes = Some(limit);
+
+        self
+    }
+
+    /// Sets whether the words given to queries are converted to lowercase when building requests.
+    /// This is the same as the lowercase rule of [with_normalization()](DatamuseClient::with_normalization)
+    /// and leaves the other rules unchanged. Patterns given to spelled_like() which contain
+    /// wildcards are left unchanged. By default this is disabled
+    pub fn lowercase_inputs(mut self, enabled: bool) -> Self {
+        self.normalization.lowercase = enabled;
+
+        self
+    }
+
+    /// Sets the rules used to normalize the words given to queries when building requests, see
+    /// [NormalizationRules](NormalizationRules). This replaces all previously set rules
+    /// (including [lowercase_inputs()](DatamuseClient::lowercase_inputs)). By default no
+    /// normalization is applied
+    pub fn with_normalization(mut self, rules: NormalizationRules) -> Self {
+        self.normalization = rules;
+
+        self
+    }
+
+    /// Sets whether building requests fails with an [Error::InsecureUrl](Error::InsecureUrl) if the
+    /// base url of the api does not use https. This prevents queries from being sent unencrypted.
+    /// By default this is enabled
+    pub fn require_https(mut self, enabled: bool) -> Self {
+        self.require_https = enabled;
+
+        self
+    }
+
+    fn set_header(&mut self, name: HeaderName, value: &str) {
+        self.headers.retain(|(key, _)| *key != name);
+        self.headers.push((name, String::from(value)));
+    }
+
+    pub(crate) fn count_request(&self) {
+        self.requests_sent.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Estimates how many requests a workload will make each day. This can be compared against
+/// the [DAILY_REQUEST_LIMIT](DAILY_REQUEST_LIMIT) to decide whether a higher usage plan is needed
+pub fn estimate_daily_requests(queries_per_user: u32, users: u32) -> u64 {
+    u64::from(queries_per_user) * u64::from(users)
+}
+
+impl Default for DatamuseClient {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// A type alias for Results with the library Error type
@@ -119,6 +269,53 @@ pub enum Error {
     VocabularyError((String, String)),
     /// An error resulting from the use of a parameter not intended for the specified endpoint
     EndPointError((String, String)),
+    /// An error resulting from a parameter which was given an invalid value or could not be parsed
+    ParameterError(String),
+    /// An error resulting from a response body larger than the limit (in bytes) set with
+    /// [with_max_response_bytes()](DatamuseClient::with_max_response_bytes)
+    ResponseTooLarge(usize),
+    /// An error resulting from a request to a url which does not use https while
+    /// [require_https()](DatamuseClient::require_https) is enabled
+    InsecureUrl(String),
+    /// An error resulting from a request which was still rejected with the given status after
+    /// the given number of attempts, see [retry()](RequestBuilder::retry)
+    RetriesExhausted(u32, reqwest::StatusCode),
+    /// An error resulting from a response with a status other than 2xx. It contains the status
+    /// and the body of the response
+    HttpStatus(reqwest::StatusCode, String),
+    /// An error returned by the api itself in place of a word list. It contains the message
+    /// given by the api
+    ApiError(String),
+}
+
+impl Error {
+    /// Returns whether the error was caused by the api rejecting the request with a status of
+    /// 429 (too many requests). In this case the request can be sent again after waiting
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(reqwest::StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Returns whether the error is likely temporary, so that sending the same request again
+    /// may succeed. This is the case for rate limiting, server errors (5xx), timeouts and failed
+    /// connections. Errors caused by the request itself (e.g. invalid parameters) are not transient
+    pub fn is_transient(&self) -> bool {
+        if let Self::ReqwestError(err) = self {
+            if err.is_timeout() || err.is_connect() {
+                return true;
+            }
+        }
+
+        matches!(self.status(), Some(status) if is_retryable(status))
+    }
+
+    //The http status the error was caused by if there is one
+    fn status(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            Self::ReqwestError(err) => err.status(),
+            Self::RetriesExhausted(_, status) | Self::HttpStatus(status, _) => Some(*status),
+            _ => None,
+        }
+    }
 }
 
 impl Display for Error {
@@ -136,11 +333,39 @@ impl Display for Error {
                 "Error: The parameter {} is not supported for {}",
                 param, endpoint
             ),
+            Self::ParameterError(message) => write!(f, "Error: {}", message),
+            Self::ResponseTooLarge(limit) => write!(
+                f,
+                "Error: The response is larger than the limit of {} bytes",
+                limit
+            ),
+            Self::InsecureUrl(url) => write!(
+                f,
+                "Error: The url {} does not use https, which is required",
+                url
+            ),
+            Self::RetriesExhausted(attempts, status) => write!(
+                f,
+                "Error: The request failed with status {} after {} attempts",
+                status, attempts
+            ),
+            Self::HttpStatus(status, _) => {
+                write!(f, "Error: The api responded with status {}", status)
+            }
+            Self::ApiError(message) => write!(f, "Error: The api returned an error: {}", message),
         }
     }
 }
 
-impl error::Error for Error {}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::ReqwestError(err) => Some(err),
+            Self::SerdeError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
 
 impl From<reqwest::Error> for Error {
     fn from(error: reqwest::Error) -> Self {
@@ -153,3 +378,188 @@ impl From<serde_json::Error> for Error {
         Error::SerdeError(error)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::{MockResponse, MockServer};
+    use crate::{DatamuseClient, EndPoint, Error, Vocabulary, DAILY_REQUEST_LIMIT};
+
+    #[test]
+    fn estimate_daily_requests() {
+        assert_eq!(0, super::estimate_daily_requests(20, 0));
+        assert_eq!(150_000, super::estimate_daily_requests(30, 5_000));
+        assert_eq!(
+            u64::from(u32::MAX) * u64::from(u32::MAX),
+            super::estimate_daily_requests(u32::MAX, u32::MAX)
+        );
+    }
+
+    #[tokio::test]
+    async fn remaining_budget() {
+        let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+        let client = server.client();
+        assert_eq!(DAILY_REQUEST_LIMIT, client.remaining_budget());
+
+        for _ in 0..2 {
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like("cow")
+                .send()
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(2, server.hits());
+        assert_eq!(DAILY_REQUEST_LIMIT - 2, client.remaining_budget());
+    }
+
+    #[test]
+    fn remaining_budget_saturates() {
+        let client = DatamuseClient::new();
+        client.requests_sent.store(
+            DAILY_REQUEST_LIMIT + 10,
+            std::sync::atomic::Ordering::Relaxed,
+        );
+
+        assert_eq!(0, client.remaining_budget());
+    }
+
+    #[test]
+    fn require_https() {
+        let client = DatamuseClient::new().with_base_url("http://api.datamuse.com/");
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow");
+        match request.build() {
+            Err(Error::InsecureUrl(url)) => assert_eq!("http://api.datamuse.com/words?ml=cow", url),
+            val => panic!("Expected InsecureUrl, got {:?}", val),
+        }
+
+        let client = client.require_https(false);
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow");
+        assert!(request.build().is_ok());
+
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow");
+        assert!(request.build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn with_client() {
+        let server = MockServer::start(vec![MockResponse::json(r#"[{"word":"milk"}]"#)]).await;
+        let reqwest_client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_secs(10))
+            .build()
+            .unwrap();
+        let client = DatamuseClient::with_client(reqwest_client)
+            .with_base_url(&server.client().base_url)
+            .require_https(false);
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .list()
+            .await
+            .unwrap();
+
+        assert_eq!("milk", word_list[0].word);
+        assert_eq!(1, server.hits());
+    }
+
+    #[test]
+    fn with_base_url() {
+        let client = DatamuseClient::new()
+            .with_base_url("http://127.0.0.1:8080")
+            .require_https(false);
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("cow")
+            .build()
+            .unwrap();
+        assert_eq!("http://127.0.0.1:8080/sug?s=cow", request.url().as_str());
+
+        let client = DatamuseClient::new().with_base_url("https://example.com/datamuse/");
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .build()
+            .unwrap();
+        assert_eq!(
+            "https://example.com/datamuse/words?ml=cow",
+            request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn error_source() {
+        use std::error::Error as _;
+
+        let err = reqwest::Client::new().get("not a url").build().unwrap_err();
+        assert!(Error::ReqwestError(err).source().is_some());
+
+        let err = serde_json::from_str::<u32>("[").unwrap_err();
+        assert!(Error::SerdeError(err).source().is_some());
+
+        assert!(Error::ParameterError(String::from("max"))
+            .source()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn with_user_agent() {
+        let server = MockServer::start(vec![MockResponse::json("[]")]).await;
+        let client = server
+            .client()
+            .with_user_agent("my-app/1.0 (me@example.com)");
+        client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .send()
+            .await
+            .unwrap();
+
+        let request = server.last_request().to_lowercase();
+        assert!(request.contains("user-agent: my-app/1.0 (me@example.com)\r\n"));
+    }
+
+    #[test]
+    fn error_classification() {
+        use reqwest::StatusCode;
+
+        let rate_limited = Error::HttpStatus(StatusCode::TOO_MANY_REQUESTS, String::new());
+        assert!(rate_limited.is_rate_limited());
+        assert!(rate_limited.is_transient());
+
+        let unavailable = Error::HttpStatus(StatusCode::SERVICE_UNAVAILABLE, String::new());
+        assert!(!unavailable.is_rate_limited());
+        assert!(unavailable.is_transient());
+
+        let bad_request = Error::HttpStatus(StatusCode::BAD_REQUEST, String::new());
+        assert!(!bad_request.is_rate_limited());
+        assert!(!bad_request.is_transient());
+
+        let exhausted = Error::RetriesExhausted(3, StatusCode::TOO_MANY_REQUESTS);
+        assert!(exhausted.is_rate_limited());
+        assert!(exhausted.is_transient());
+
+        assert!(!Error::ParameterError(String::from("max")).is_transient());
+    }
+
+    #[tokio::test]
+    async fn reqwest_error_classification() {
+        let server = MockServer::start(vec![MockResponse::status(429, "")]).await;
+        let err = reqwest::get(&server.client().base_url)
+            .await
+            .unwrap()
+            .error_for_status()
+            .unwrap_err();
+
+        let err = Error::from(err);
+        assert!(err.is_rate_limited());
+        assert!(err.is_transient());
+    }
+}
@@ -0,0 +1,969 @@
+use crate::request::{
+    EndPoint, MetaDataFlag, PronunciationFormat, RelatedType, RequestBuilder, Vocabulary,
+};
+use crate::response::{Definition, RhymeMatch, WordElement};
+use crate::{DatamuseClient, Error, Result};
+use futures::future;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+impl DatamuseClient {
+    /// Sends both queries with the [WordFrequency](MetaDataFlag::WordFrequency) flag and returns
+    /// the words which are prominent in the results of the topic query but rare in the baseline
+    /// (similar to TF-IDF). The scores of each result set are first normalized by the highest
+    /// score in that set (missing scores count as 0).
+    /// A word is kept if its normalized topic score is higher than its normalized baseline score
+    /// (words missing from the baseline count as 0) and the kept words are ordered by the ratio
+    /// `topic / ((baseline + 1) * (1 + ln(1 + frequency)))`, from most to least distinctive.
+    /// The frequency is the number of occurrences per million words (missing frequencies count
+    /// as 0), so words which are common in general rank lower. The returned elements are the
+    /// ones from the topic query and keep their original scores
+    pub async fn distinctive_words(
+        &self,
+        topic_query: RequestBuilder<'_>,
+        baseline_query: RequestBuilder<'_>,
+    ) -> Result<Vec<WordElement>> {
+        let topic = topic_query.with_frequency_flag().list().await?;
+        let baseline = baseline_query.with_frequency_flag().list().await?;
+
+        Ok(distinctive(topic, &baseline))
+    }
+
+    /// Sends one query per given related type for the given word at the same time and returns
+    /// the results of each query in a map with the related type as the key. The queries use the
+    /// English vocabulary. If any of the queries fails, its error is returned
+    pub async fn related_breakdown(
+        &self,
+        word: &str,
+        types: &[RelatedType],
+    ) -> Result<HashMap<RelatedType, Vec<WordElement>>> {
+        let queries = types.iter().map(|rel_type| async move {
+            self.new_query(Vocabulary::English, EndPoint::Words)
+                .related(*rel_type, word)
+                .list()
+                .await
+                .map(|word_list| (*rel_type, word_list))
+        });
+
+        let results = future::try_join_all(queries).await?;
+
+        Ok(results.into_iter().collect())
+    }
+
+    /// Sends a rhyme query for each of the given words at the same time and returns the words
+    /// which rhyme with any of them, together with the seeds they rhyme with. Words found for
+    /// multiple seeds are only returned once with the highest of their scores. The results are
+    /// ordered by this score from highest to lowest
+    pub async fn rhymes_with_any(&self, words: &[&str]) -> Result<Vec<RhymeMatch>> {
+        let queries = words.iter().map(|word| async move {
+            self.new_query(Vocabulary::English, EndPoint::Words)
+                .related(RelatedType::Rhyme, word)
+                .list()
+                .await
+                .map(|word_list| (*word, word_list))
+        });
+
+        let results = future::try_join_all(queries).await?;
+
+        Ok(merge_rhymes(results))
+    }
+
+    /// Returns whether the word b is among the rhymes of the word a (ignoring case).
+    /// The rhymes are taken from the English vocabulary
+    pub async fn do_they_rhyme(&self, a: &str, b: &str) -> Result<bool> {
+        Ok(self.find_rhyme(a, b).await?.is_some())
+    }
+
+    /// Returns the score of the word b among the rhymes of the word a (ignoring case), or None
+    /// if b does not rhyme with a or the api returned no score. The rhymes are taken from the
+    /// English vocabulary
+    pub async fn rhyme_strength(&self, a: &str, b: &str) -> Result<Option<usize>> {
+        Ok(self.find_rhyme(a, b).await?.and_then(|elem| elem.score))
+    }
+
+    /// Queries the words which sound like the given word and groups them by their IPA
+    /// pronunciation. Words within the same group are pronounced identically. Words for which
+    /// the api returns no pronunciation are left out. The words keep their order within each group
+    pub async fn soundalike_clusters(
+        &self,
+        word: &str,
+    ) -> Result<HashMap<String, Vec<WordElement>>> {
+        let word_list = self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .sounds_like(word)
+            .meta_data(MetaDataFlag::Pronunciation(PronunciationFormat::Ipa))
+            .list()
+            .await?;
+
+        let mut clusters: HashMap<String, Vec<WordElement>> = HashMap::new();
+        for elem in word_list {
+            if let Some(pronunciation) = &elem.pronunciation_ipa {
+                clusters
+                    .entry(pronunciation.trim().to_string())
+                    .or_default()
+                    .push(elem);
+            }
+        }
+
+        Ok(clusters)
+    }
+
+    /// Looks up the definitions of the given word and returns the gloss of each definition
+    /// together with its example sentences (see [Definition](crate::Definition)). This returns
+    /// an empty list if the api has no definitions for the word
+    pub async fn define_with_examples(&self, word: &str) -> Result<Vec<(String, Vec<String>)>> {
+        let definitions = self.exact_definitions(word).await?;
+
+        Ok(definitions
+            .iter()
+            .map(|def| (def.gloss(), def.examples()))
+            .collect())
+    }
+
+    /// Returns the definitions of exactly the given word from the English vocabulary. The
+    /// definitions are cached by this client, so looking up the same word again within the
+    /// given time to live returns the cached definitions without sending another request.
+    /// Once the time to live has passed, the definitions are requested and cached again. The
+    /// definitions of at most [DEFINITION_CACHE_CAPACITY](crate::DEFINITION_CACHE_CAPACITY)
+    /// words are cached, after which the least recently looked up word is removed. Words are
+    /// cached after applying the [normalization rules](DatamuseClient::with_normalization) of the
+    /// client, so e.g. "Run" and "run" share an entry if lowercasing is enabled
+    pub async fn define_cached(&self, word: &str, ttl: Duration) -> Result<Vec<Definition>> {
+        let word = self.normalization.apply(word);
+        {
+            let mut cache = self.definition_cache.lock().unwrap();
+            match cache.get(&word) {
+                Some((cached_at, definitions)) if cached_at.elapsed() < ttl => {
+                    return Ok(definitions.clone())
+                }
+                Some(_) => cache.remove(&word), //Expired
+                None => (),
+            }
+        }
+
+        let definitions = self.exact_definitions(&word).await?;
+        self.definition_cache
+            .lock()
+            .unwrap()
+            .insert(word, (Instant::now(), definitions.clone()));
+
+        Ok(definitions)
+    }
+
+    /// Returns words from the English vocabulary which rhyme with the given word. This is a
+    /// shorthand for a words query with [RelatedType::Rhyme](RelatedType::Rhyme)
+    pub async fn rhymes(&self, word: &str) -> Result<Vec<WordElement>> {
+        self.related_words(RelatedType::Rhyme, word).await
+    }
+
+    /// Returns the synonyms of the given word from the English vocabulary. This is a shorthand
+    /// for a words query with [RelatedType::Synonym](RelatedType::Synonym)
+    pub async fn synonyms(&self, word: &str) -> Result<Vec<WordElement>> {
+        self.related_words(RelatedType::Synonym, word).await
+    }
+
+    /// Returns the antonyms of the given word from the English vocabulary. This is a shorthand
+    /// for a words query with [RelatedType::Antonym](RelatedType::Antonym)
+    pub async fn antonyms(&self, word: &str) -> Result<Vec<WordElement>> {
+        self.related_words(RelatedType::Antonym, word).await
+    }
+
+    /// Returns the best synonym of the given word from the English vocabulary or None if the
+    /// api has no synonyms for it
+    pub async fn top_synonym(&self, word: &str) -> Result<Option<String>> {
+        Ok(self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Synonym, word)
+            .max_results(1)
+            .list()
+            .await?
+            .into_iter()
+            .next()
+            .map(|elem| elem.word))
+    }
+
+    /// Returns the synonyms of the given word from the English vocabulary which are related to
+    /// the given topic. This can be used to build a thesaurus for a specific domain
+    pub async fn synonyms_in_topic(&self, word: &str, topic: &str) -> Result<Vec<WordElement>> {
+        self.new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Synonym, word)
+            .add_topic(topic)
+            .list()
+            .await
+    }
+
+    /// Returns words from the Spanish vocabulary with a meaning similar to the given word. As
+    /// related() (and with it synonyms) is not available for the Spanish vocabulary, this can be
+    /// used as a thesaurus instead. Note that the results are only similar in meaning and may
+    /// also include other associated words which are not synonyms
+    pub async fn spanish_means_like(&self, word: &str) -> Result<Vec<WordElement>> {
+        self.new_query(Vocabulary::Spanish, EndPoint::Words)
+            .means_like(word)
+            .list()
+            .await
+    }
+
+    /// Sends a means_like query for each of the two words at the same time and returns the
+    /// words found in both results, i.e. words whose meaning is between the two words. The score
+    /// of each returned word is the lower of its two scores (None if either is missing) and the
+    /// words are ordered by this score from highest to lowest
+    pub async fn common_meaning(&self, a: &str, b: &str) -> Result<Vec<WordElement>> {
+        let query_a = self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like(a);
+        let query_b = self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like(b);
+        let (list_a, list_b) = future::try_join(query_a.list(), query_b.list()).await?;
+
+        Ok(intersect_by_min_score(list_a, &list_b))
+    }
+
+    async fn find_rhyme(&self, a: &str, b: &str) -> Result<Option<WordElement>> {
+        let rhymes = self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Rhyme, a)
+            .max_results(1000)
+            .list()
+            .await?;
+
+        Ok(rhymes
+            .into_iter()
+            .find(|elem| elem.word.to_lowercase() == b.to_lowercase()))
+    }
+
+    /// Returns English words which start with the given letter and, if given, have a meaning
+    /// similar to the given word. This can be used to generate acrostics or alphabet books.
+    /// Only results starting with the letter (ignoring case) are returned. An
+    /// [Error::ParameterError](crate::Error::ParameterError) is returned if the letter is not
+    /// alphabetic
+    pub async fn words_starting_with(
+        &self,
+        letter: char,
+        meaning: Option<&str>,
+    ) -> Result<Vec<WordElement>> {
+        if !letter.is_alphabetic() {
+            return Err(Error::ParameterError(format!(
+                "The letter {:?} is not alphabetic",
+                letter
+            )));
+        }
+
+        let mut request = self.new_query(Vocabulary::English, EndPoint::Words);
+        if let Some(word) = meaning {
+            request = request.means_like(word);
+        }
+        let mut word_list = request.spelled_like(&format!("{}*", letter)).list().await?;
+
+        word_list.retain(|elem| {
+            matches!(elem.word.chars().next(), Some(first) if first.to_lowercase().eq(letter.to_lowercase()))
+        });
+
+        Ok(word_list)
+    }
+
+    /// Returns the completions of the given prefix from the "suggest" endpoint which consist of
+    /// multiple words, ordered by score from highest to lowest. This can be used to autocomplete
+    /// whole phrases such as "hello world". At most max results are requested from the api,
+    /// so fewer phrases may be returned
+    pub async fn phrase_completions(&self, prefix: &str, max: u16) -> Result<Vec<WordElement>> {
+        let mut word_list = self
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string(prefix)
+            .max_results(max)
+            .list()
+            .await?;
+
+        word_list.retain(|elem| elem.word.split_whitespace().count() > 1);
+        word_list.sort_by_key(|elem| Reverse(elem.score));
+
+        Ok(word_list)
+    }
+
+    /// Returns how similar the ARPABET pronunciations of the two words are as a value between 0.0
+    /// (nothing in common) and 1.0 (same pronunciation). This is one minus the edit distance
+    /// between the phonemes of the words divided by the number of phonemes of the longer word.
+    /// Stress markers are ignored, so "record" as a noun and as a verb are equal. If the api has
+    /// no pronunciation for either word, 0.0 is returned
+    pub async fn pronunciation_similarity(&self, a: &str, b: &str) -> Result<f32> {
+        let (word_a, word_b) = future::try_join(self.arpabet_word(a), self.arpabet_word(b)).await?;
+
+        let phonemes_a = word_a.as_ref().and_then(|elem| elem.arpabet_tokens());
+        let phonemes_b = word_b.as_ref().and_then(|elem| elem.arpabet_tokens());
+        match (phonemes_a, phonemes_b) {
+            (Some(phonemes_a), Some(phonemes_b)) => {
+                Ok(phoneme_similarity(&phonemes_a, &phonemes_b))
+            }
+            _ => Ok(0.0),
+        }
+    }
+
+    async fn related_words(&self, rel_type: RelatedType, word: &str) -> Result<Vec<WordElement>> {
+        self.new_query(Vocabulary::English, EndPoint::Words)
+            .related(rel_type, word)
+            .list()
+            .await
+    }
+
+    async fn arpabet_word(&self, word: &str) -> Result<Option<WordElement>> {
+        Ok(self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like(word)
+            .meta_data(MetaDataFlag::Pronunciation(PronunciationFormat::Arpabet))
+            .max_results(1)
+            .list()
+            .await?
+            .into_iter()
+            .find(|elem| elem.word.to_lowercase() == word.to_lowercase()))
+    }
+
+    async fn exact_definitions(&self, word: &str) -> Result<Vec<Definition>> {
+        Ok(self
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like(word)
+            .meta_data(MetaDataFlag::Definitions)
+            .max_results(1)
+            .list()
+            .await?
+            .into_iter()
+            .find(|elem| elem.word == word)
+            .and_then(|elem| elem.definitions)
+            .unwrap_or_default())
+    }
+}
+
+fn distinctive(topic: Vec<WordElement>, baseline: &[WordElement]) -> Vec<WordElement> {
+    let topic_max = max_score(&topic);
+    let baseline_max = max_score(baseline);
+
+    let baseline_scores: HashMap<&str, f64> = baseline
+        .iter()
+        .map(|elem| {
+            (
+                elem.word.as_str(),
+                elem.score.unwrap_or(0) as f64 / baseline_max,
+            )
+        })
+        .collect();
+
+    let mut ranked: Vec<(f64, WordElement)> = Vec::new();
+    for elem in topic {
+        let topic_score = elem.score.unwrap_or(0) as f64 / topic_max;
+        let baseline_score = baseline_scores
+            .get(elem.word.as_str())
+            .copied()
+            .unwrap_or(0.0);
+
+        if topic_score > baseline_score {
+            let rarity = 1.0 + elem.frequency.unwrap_or(0.0).ln_1p();
+            ranked.push((topic_score / ((baseline_score + 1.0) * rarity), elem));
+        }
+    }
+
+    ranked.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+    ranked.into_iter().map(|(_, elem)| elem).collect()
+}
+
+fn intersect_by_min_score(list_a: Vec<WordElement>, list_b: &[WordElement]) -> Vec<WordElement> {
+    let scores_b: HashMap<&str, Option<usize>> = list_b
+        .iter()
+        .map(|elem| (elem.word.as_str(), elem.score))
+        .collect();
+
+    let mut intersection: Vec<WordElement> = list_a
+        .into_iter()
+        .filter_map(|mut elem| {
+            let score_b = *scores_b.get(elem.word.as_str())?;
+            elem.score = match (elem.score, score_b) {
+                (Some(score_a), Some(score_b)) => Some(score_a.min(score_b)),
+                _ => None,
+            };
+
+            Some(elem)
+        })
+        .collect();
+
+    //Option orders None first, so reversing it places missing scores last
+    intersection.sort_by_key(|elem| Reverse(elem.score));
+    intersection
+}
+
+//One minus the normalized edit distance of the phonemes without stress markers
+fn phoneme_similarity(a: &[&str], b: &[&str]) -> f32 {
+    let a: Vec<&str> = a
+        .iter()
+        .map(|phoneme| phoneme.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .collect();
+    let b: Vec<&str> = b
+        .iter()
+        .map(|phoneme| phoneme.trim_end_matches(|c: char| c.is_ascii_digit()))
+        .collect();
+
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, phoneme_a) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, phoneme_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(phoneme_a != phoneme_b);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    1.0 - previous[b.len()] as f32 / longest as f32
+}
+
+fn merge_rhymes(results: Vec<(&str, Vec<WordElement>)>) -> Vec<RhymeMatch> {
+    let mut merged: Vec<RhymeMatch> = Vec::new();
+    let mut indices: HashMap<String, usize> = HashMap::new();
+
+    for (seed, word_list) in results {
+        for elem in word_list {
+            match indices.get(&elem.word) {
+                Some(&i) => {
+                    let rhyme = &mut merged[i];
+                    if !rhyme.seeds.iter().any(|val| val == seed) {
+                        rhyme.seeds.push(String::from(seed));
+                    }
+                    if elem.score > rhyme.element.score {
+                        rhyme.element = elem;
+                    }
+                }
+                None => {
+                    indices.insert(elem.word.clone(), merged.len());
+                    merged.push(RhymeMatch {
+                        element: elem,
+                        seeds: vec![String::from(seed)],
+                    });
+                }
+            }
+        }
+    }
+
+    merged.sort_by_key(|rhyme| Reverse(rhyme.element.score));
+    merged
+}
+
+fn max_score(words: &[WordElement]) -> f64 {
+    let max = words
+        .iter()
+        .filter_map(|elem| elem.score)
+        .max()
+        .unwrap_or(0);
+
+    if max == 0 {
+        1.0 //Avoids dividing by zero, all normalized scores are then 0
+    } else {
+        max as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_server::{fixture, MockResponse, MockServer};
+    use crate::{EndPoint, Error, RelatedType, Vocabulary};
+    use std::time::Duration;
+    use tokio::time;
+
+    #[test]
+    fn distinctive_words() {
+        let topic = fixture(
+            r#"[
+                {"word":"ocean","score":1000,"tags":["f:50.0"]},
+                {"word":"water","score":900,"tags":["f:300.0"]},
+                {"word":"tide","score":500,"tags":["f:1.0"]},
+                {"word":"wave","score":150},
+                {"word":"blue","score":100,"tags":["f:100.0"]}
+            ]"#,
+        );
+        let baseline = fixture(
+            r#"[
+                {"word":"water","score":2000},
+                {"word":"blue","score":1000},
+                {"word":"tide","score":100}
+            ]"#,
+        );
+
+        let actual: Vec<String> = super::distinctive(topic, &baseline)
+            .into_iter()
+            .map(|elem| elem.word)
+            .collect();
+
+        //tide: 0.5 / (1.05 * 1.69), ocean: 1.0 / (1.0 * 4.93), wave: 0.15 / 1.0,
+        //water and blue are more prominent in the baseline
+        assert_eq!(vec!["tide", "ocean", "wave"], actual);
+    }
+
+    #[tokio::test]
+    async fn distinctive_words_frequency_flag() {
+        let server = MockServer::routes(vec![
+            (
+                "ml=ocean&md=f",
+                MockResponse::json(r#"[{"word":"tide","score":10,"tags":["f:1.0"]}]"#),
+            ),
+            (
+                "ml=water&md=f",
+                MockResponse::json(r#"[{"word":"rain","score":100,"tags":["f:50.0"]}]"#),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .distinctive_words(
+                client
+                    .new_query(Vocabulary::English, EndPoint::Words)
+                    .means_like("ocean"),
+                client
+                    .new_query(Vocabulary::English, EndPoint::Words)
+                    .means_like("water"),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(2, server.hits());
+        assert_eq!(1, word_list.len());
+        assert_eq!("tide", word_list[0].word);
+        assert_eq!(Some(1.0), word_list[0].frequency);
+    }
+
+    #[test]
+    fn distinctive_words_empty_baseline() {
+        let topic = fixture(r#"[{"word":"ocean","score":10},{"word":"tide","score":20}]"#);
+
+        let actual: Vec<String> = super::distinctive(topic, &[])
+            .into_iter()
+            .map(|elem| elem.word)
+            .collect();
+
+        assert_eq!(vec!["tide", "ocean"], actual);
+    }
+
+    #[tokio::test]
+    async fn related_breakdown() {
+        let server = MockServer::routes(vec![
+            (
+                "rel_syn=happy",
+                MockResponse::json(r#"[{"word":"glad","score":10},{"word":"content","score":5}]"#),
+            ),
+            (
+                "rel_ant=happy",
+                MockResponse::json(r#"[{"word":"sad","score":10}]"#),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let breakdown = client
+            .related_breakdown("happy", &[RelatedType::Synonym, RelatedType::Antonym])
+            .await
+            .unwrap();
+
+        assert_eq!(2, server.hits());
+        assert_eq!(2, breakdown.len());
+        assert_eq!(2, breakdown[&RelatedType::Synonym].len());
+        assert_eq!("glad", breakdown[&RelatedType::Synonym][0].word);
+        assert_eq!(1, breakdown[&RelatedType::Antonym].len());
+        assert_eq!("sad", breakdown[&RelatedType::Antonym][0].word);
+    }
+
+    #[tokio::test]
+    async fn do_they_rhyme() {
+        let server = MockServer::routes(vec![(
+            "rel_rhy=cat",
+            MockResponse::json(r#"[{"word":"hat","score":300},{"word":"bat","score":200}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        assert!(client.do_they_rhyme("cat", "HAT").await.unwrap());
+        assert!(!client.do_they_rhyme("cat", "dog").await.unwrap());
+        assert_eq!(
+            Some(200),
+            client.rhyme_strength("cat", "bat").await.unwrap()
+        );
+        assert_eq!(None, client.rhyme_strength("cat", "dog").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn soundalike_clusters() {
+        let server = MockServer::routes(vec![(
+            "ipa=1&sl=there&md=r",
+            MockResponse::json(
+                r#"[
+                    {"word":"there","score":300,"tags":["ipa_pron:ðɛɹ"]},
+                    {"word":"their","score":200,"tags":["ipa_pron:ðɛɹ"]},
+                    {"word":"they're","score":150,"tags":["ipa_pron:ðɛɹ"]},
+                    {"word":"dare","score":100,"tags":["ipa_pron:dɛɹ"]},
+                    {"word":"thar","score":50}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let clusters = client.soundalike_clusters("there").await.unwrap();
+
+        assert_eq!(2, clusters.len());
+        let words: Vec<&str> = clusters["ðɛɹ"]
+            .iter()
+            .map(|elem| elem.word.as_str())
+            .collect();
+        assert_eq!(vec!["there", "their", "they're"], words);
+        assert_eq!("dare", clusters["dɛɹ"][0].word);
+    }
+
+    #[tokio::test]
+    async fn define_with_examples() {
+        let server = MockServer::routes(vec![(
+            "sp=run&max=1&md=d",
+            MockResponse::json(
+                r#"[{"word":"run","score":100,"defs":[
+                    "v\tmove fast by using one's feet; \"He ran to the store\"",
+                    "n\ta score in baseball"
+                ]}]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let definitions = client.define_with_examples("run").await.unwrap();
+
+        assert_eq!(
+            vec![
+                (
+                    String::from("move fast by using one's feet"),
+                    vec![String::from("He ran to the store")]
+                ),
+                (String::from("a score in baseball"), Vec::new()),
+            ],
+            definitions
+        );
+        assert!(client.define_with_examples("xyz").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn top_synonym() {
+        let server = MockServer::routes(vec![(
+            "rel_syn=happy&max=1",
+            MockResponse::json(r#"[{"word":"glad","score":300}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        assert_eq!(
+            Some(String::from("glad")),
+            client.top_synonym("happy").await.unwrap()
+        );
+        assert_eq!(None, client.top_synonym("xyzzy").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn common_meaning() {
+        let server = MockServer::routes(vec![
+            (
+                "ml=sea",
+                MockResponse::json(
+                    r#"[{"word":"ocean","score":300},{"word":"wave","score":200},{"word":"tide","score":100}]"#,
+                ),
+            ),
+            (
+                "ml=sky",
+                MockResponse::json(
+                    r#"[{"word":"blue","score":300},{"word":"tide","score":250},{"word":"wave","score":50}]"#,
+                ),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let actual: Vec<(String, Option<usize>)> = client
+            .common_meaning("sea", "sky")
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|elem| (elem.word, elem.score))
+            .collect();
+
+        assert_eq!(
+            vec![
+                (String::from("tide"), Some(100)),
+                (String::from("wave"), Some(50)),
+            ],
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn define_cached() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"run","score":100,"defs":["v\tmove fast by using one's feet"]}]"#,
+        )])
+        .await;
+        let client = server.client();
+        let ttl = Duration::from_secs(60);
+        time::pause();
+
+        let definitions = client.define_cached("run", ttl).await.unwrap();
+        assert_eq!(1, definitions.len());
+        assert_eq!("move fast by using one's feet", definitions[0].definition);
+
+        let cached = client.define_cached("run", ttl).await.unwrap();
+        assert_eq!(definitions, cached);
+        assert_eq!(1, server.hits());
+
+        time::advance(ttl).await;
+        let refreshed = client.define_cached("run", ttl).await.unwrap();
+        assert_eq!(definitions, refreshed);
+        assert_eq!(2, server.hits());
+    }
+
+    #[tokio::test]
+    async fn define_cached_normalized() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"run","score":100,"defs":["v\tmove fast"]}]"#,
+        )])
+        .await;
+        let client = server.client().lowercase_inputs(true);
+        let ttl = Duration::from_secs(60);
+
+        let definitions = client.define_cached("Run", ttl).await.unwrap();
+        assert_eq!(1, definitions.len());
+
+        let cached = client.define_cached("run", ttl).await.unwrap();
+        assert_eq!(definitions, cached);
+        assert_eq!(1, server.hits());
+        assert!(server.last_request().contains("sp=run&"));
+    }
+
+    #[tokio::test]
+    async fn define_cached_removes_expired() {
+        let server = MockServer::start(vec![
+            MockResponse::json(r#"[{"word":"run","score":100,"defs":["v\tmove fast"]}]"#),
+            MockResponse::status(503, ""),
+        ])
+        .await;
+        let client = server.client();
+        let ttl = Duration::from_secs(60);
+        time::pause();
+
+        client.define_cached("run", ttl).await.unwrap();
+        assert_eq!(1, client.definition_cache.lock().unwrap().len());
+
+        time::advance(ttl).await;
+        assert!(client.define_cached("run", ttl).await.is_err());
+        assert_eq!(0, client.definition_cache.lock().unwrap().len());
+    }
+
+    #[tokio::test]
+    async fn words_starting_with() {
+        let server = MockServer::routes(vec![
+            (
+                "ml=ocean&sp=s*",
+                MockResponse::json(
+                    r#"[
+                        {"word":"sea","score":300},
+                        {"word":"salt water","score":200},
+                        {"word":"deep sea","score":100}
+                    ]"#,
+                ),
+            ),
+            (
+                "?sp=b*",
+                MockResponse::json(r#"[{"word":"bat","score":100}]"#),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .words_starting_with('s', Some("ocean"))
+            .await
+            .unwrap();
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["sea", "salt water"], words);
+
+        let word_list = client.words_starting_with('b', None).await.unwrap();
+        assert_eq!("bat", word_list[0].word);
+
+        match client.words_starting_with('3', None).await {
+            Err(Error::ParameterError(_)) => (),
+            val => panic!("Expected ParameterError, got {:?}", val),
+        }
+        assert_eq!(2, server.hits());
+    }
+
+    #[tokio::test]
+    async fn phrase_completions() {
+        let server = MockServer::routes(vec![(
+            "s=hello+w&max=5",
+            MockResponse::json(
+                r#"[
+                    {"word":"hello","score":900},
+                    {"word":"hello world","score":500},
+                    {"word":"hellow","score":400},
+                    {"word":"hello kitty","score":700}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client.phrase_completions("hello w", 5).await.unwrap();
+
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["hello kitty", "hello world"], words);
+    }
+
+    #[tokio::test]
+    async fn synonyms_in_topic() {
+        let server = MockServer::routes(vec![(
+            "rel_syn=bank&topics=river",
+            MockResponse::json(r#"[{"word":"shore","score":100}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client.synonyms_in_topic("bank", "river").await.unwrap();
+
+        assert_eq!(1, word_list.len());
+        assert_eq!("shore", word_list[0].word);
+    }
+
+    #[tokio::test]
+    async fn pronunciation_similarity() {
+        let server = MockServer::routes(vec![
+            (
+                "sp=cat&",
+                MockResponse::json(r#"[{"word":"cat","score":100,"tags":["pron:K AE1 T "]}]"#),
+            ),
+            (
+                "sp=hat&",
+                MockResponse::json(r#"[{"word":"hat","score":100,"tags":["pron:HH AE1 T "]}]"#),
+            ),
+            (
+                "sp=dog&",
+                MockResponse::json(r#"[{"word":"dog","score":100,"tags":["pron:D AO1 G "]}]"#),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let rhyming = client.pronunciation_similarity("cat", "hat").await.unwrap();
+        assert!((rhyming - 2.0 / 3.0).abs() < 1e-6);
+
+        let different = client.pronunciation_similarity("cat", "dog").await.unwrap();
+        assert_eq!(0.0, different);
+
+        let unknown = client
+            .pronunciation_similarity("cat", "xyzzy")
+            .await
+            .unwrap();
+        assert_eq!(0.0, unknown);
+    }
+
+    #[test]
+    fn phoneme_similarity() {
+        let noun = ["R", "EH1", "K", "ER0", "D"];
+        let verb = ["R", "IH0", "K", "AO1", "R", "D"];
+
+        assert_eq!(1.0, super::phoneme_similarity(&noun, &noun));
+        assert_eq!(1.0, super::phoneme_similarity(&["AE1"], &["AE0"]));
+        assert!((super::phoneme_similarity(&noun, &verb) - 0.5).abs() < 1e-6);
+        assert_eq!(0.0, super::phoneme_similarity(&noun, &[]));
+    }
+
+    #[tokio::test]
+    async fn spanish_means_like() {
+        let server = MockServer::routes(vec![(
+            "v=es&ml=perro",
+            MockResponse::json(r#"[{"word":"can","score":100}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client.spanish_means_like("perro").await.unwrap();
+
+        assert_eq!(1, word_list.len());
+        assert_eq!("can", word_list[0].word);
+    }
+
+    #[tokio::test]
+    async fn rhymes_with_any() {
+        let server = MockServer::routes(vec![
+            (
+                "rel_rhy=cat",
+                MockResponse::json(
+                    r#"[
+                        {"word":"hat","score":500},
+                        {"word":"that","score":300},
+                        {"word":"bat","score":100}
+                    ]"#,
+                ),
+            ),
+            (
+                "rel_rhy=sat",
+                MockResponse::json(
+                    r#"[
+                        {"word":"that","score":400},
+                        {"word":"mat","score":200}
+                    ]"#,
+                ),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let rhymes = client.rhymes_with_any(&["cat", "sat"]).await.unwrap();
+
+        let actual: Vec<(&str, Option<usize>, Vec<&str>)> = rhymes
+            .iter()
+            .map(|rhyme| {
+                (
+                    rhyme.element.word.as_str(),
+                    rhyme.element.score,
+                    rhyme.seeds.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                ("hat", Some(500), vec!["cat"]),
+                ("that", Some(400), vec!["cat", "sat"]),
+                ("mat", Some(200), vec!["sat"]),
+                ("bat", Some(100), vec!["cat"]),
+            ],
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn rhymes_synonyms_antonyms() {
+        let server = MockServer::routes(vec![
+            (
+                "/words?rel_rhy=cat ",
+                MockResponse::json(r#"[{"word":"hat","score":100}]"#),
+            ),
+            (
+                "/words?rel_syn=happy ",
+                MockResponse::json(r#"[{"word":"glad","score":100}]"#),
+            ),
+            (
+                "/words?rel_ant=happy ",
+                MockResponse::json(r#"[{"word":"sad","score":100}]"#),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        assert_eq!("hat", client.rhymes("cat").await.unwrap()[0].word);
+        assert_eq!("glad", client.synonyms("happy").await.unwrap()[0].word);
+        assert_eq!("sad", client.antonyms("happy").await.unwrap()[0].word);
+    }
+}
@@ -1,14 +1,29 @@
-use crate::response::{Response, WordElement};
+use crate::response::{Response, SourcedWord, WordElement};
 use crate::{DatamuseClient, Error, Result};
-use reqwest;
+use futures::{future, stream, Stream};
+use serde::Deserialize;
+use std::collections::HashSet;
 use std::fmt::{self, Display, Formatter};
+use std::time::Duration;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+//The highest number of results the api returns for a single request
+const MAX_RESULTS_LIMIT: u16 = 1000;
+//The highest number of topics the api accepts for a single request
+const MAX_TOPICS: usize = 5;
+//The characters which spelled_like() patterns treat as wildcards
+const SPELLING_WILDCARDS: [char; 4] = ['?', '*', '#', '@'];
+//The delay before the first retry, which is doubled for every further retry
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
 
 /// Use this struct to build requests to send to the Datamuse api.
 /// This request can be sent either by building it into a Request with build()
 /// and then using the send() method on the resulting Request or using send() to
 /// send it directly. Note that not all parameters can be used for each vocabulary
-/// and endpoint
-#[derive(Debug)]
+/// and endpoint. It can be cloned to reuse the parameters set so far as the base of
+/// multiple queries
+#[derive(Clone, Debug)]
 pub struct RequestBuilder<'a> {
     client: &'a DatamuseClient,
     endpoint: EndPoint,
@@ -16,13 +31,24 @@ pub struct RequestBuilder<'a> {
     parameters: Vec<Parameter>,
     topics: Vec<String>, //Makes adding topics make easier, later added to parameters
     meta_data_flags: Vec<MetaDataFlag>, //Same issue as topics
+    filters: Vec<ResultFilter>, //Applied to the word list by list()
+    timeout: Option<Duration>,
+    max_attempts: u32,
+    raw_params: Vec<(String, String)>, //Added to the url as is without any validation
+    allow_unverified: bool,            //Skips the vocabulary checks of the parameters
+    query_echo: Option<QueryEcho>,
+    strict_vocabulary: bool, //Rejects related types not supported by the vocabulary
 }
 
 /// This struct represents a built request that can be sent using the send() method
 #[derive(Debug)]
 pub struct Request<'a> {
-    client: &'a reqwest::Client,
+    client: &'a DatamuseClient,
     request: reqwest::Request,
+    timeout: Option<Duration>,
+    max_attempts: u32,
+    allow_unverified: bool,
+    strict_vocabulary: bool,
 }
 
 /// This enum represents the different endpoints of the Datamuse api.
@@ -30,7 +56,7 @@ pub struct Request<'a> {
 /// whereas the "suggest" endpoint returns suggestions for words based on a
 /// hint string (autocomplete).
 /// For more detailed information visit the [Datamuse website](https://www.datamuse.com/api/)
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize)]
 pub enum EndPoint {
     /// The "words" endpoint (the official endpoint is also "/words")
     Words,
@@ -42,7 +68,7 @@ pub enum EndPoint {
 /// a source for the requests. There are currently two language options
 /// (English or Spanish) and an alternative English option from wikipedia.
 /// For more detailed information visit the [Datamuse website](https://www.datamuse.com/api/)
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize)]
 pub enum Vocabulary {
     /// The default vocabulary list with 550,000 words
     English,
@@ -56,7 +82,7 @@ pub enum Vocabulary {
 /// These parameters can be combined in any possible configuration, although very specific
 /// queries can limit results. Each option is shortly explained below.
 /// For more detailed information for each type visit the [Datamuse website](https://www.datamuse.com/api/)
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
 pub enum RelatedType {
     /// This parameter returns nouns that are typically modified by the given adjective
     NounModifiedBy,
@@ -92,7 +118,7 @@ pub enum RelatedType {
 
 /// This enum represents the various flags which can be set for retrieving metadata for each word.
 /// These metadata flags can be combined in any manner. Each is shortly described below
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize)]
 pub enum MetaDataFlag {
     /// Provides definitions for each of the words in the response
     Definitions,
@@ -109,7 +135,7 @@ pub enum MetaDataFlag {
 
 /// This enum represents the ways pronunciations returned by the "Pronunciation" metadata flag
 /// can be given
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize)]
 pub enum PronunciationFormat {
     /// The [ARPABET](https://en.wikipedia.org/wiki/ARPABET) pronunciation format
     Arpabet,
@@ -117,6 +143,84 @@ pub enum PronunciationFormat {
     Ipa,
 }
 
+/// This enum represents the ways the results of the "suggest" endpoint can be ordered by
+/// [suggestions_sorted()](RequestBuilder::suggestions_sorted). Results which are equal
+/// under the chosen order keep the order given by the api
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SuggestSort {
+    /// The order in which the api returned the results
+    ApiOrder,
+    /// Alphabetical order of the words
+    Alphabetical,
+    /// Shortest words first
+    ByLength,
+}
+
+/// This enum represents the parameters of a query which can be echoed back by the api with
+/// [query_echo()](RequestBuilder::query_echo). The echoed word is added as the first result
+/// and can be used to look up the metadata of that word itself
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum QueryEcho {
+    /// Echoes the word given to [means_like()](RequestBuilder::means_like)
+    MeansLike,
+    /// Echoes the word given to [sounds_like()](RequestBuilder::sounds_like)
+    SoundsLike,
+    /// Echoes the word given to [spelled_like()](RequestBuilder::spelled_like)
+    SpelledLike,
+}
+
+/// This struct specifies how the words given to queries are normalized before being sent, which
+/// can be set for all requests of a client with
+/// [with_normalization()](crate::DatamuseClient::with_normalization). The rules apply to all words
+/// and topics, but not to spelled_like() patterns which contain wildcards. By default all rules
+/// are disabled
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NormalizationRules {
+    /// Removes whitespace at the start and end of words
+    pub trim: bool,
+    /// Converts words to lowercase
+    pub lowercase: bool,
+    /// Removes accents and other diacritical marks from letters (e.g. "café" becomes "cafe")
+    pub strip_accents: bool,
+    /// Replaces each sequence of whitespace with a single space and removes whitespace at the start
+    /// and end of words
+    pub collapse_whitespace: bool,
+}
+
+/// This struct describes a query declaratively, for example when loading it from a
+/// configuration file. Each field which is set is applied to a new [RequestBuilder](RequestBuilder)
+/// by [query_from_config()](crate::DatamuseClient::query_from_config) using the builder method
+/// of the same name. Unset fields are ignored and the vocabulary and endpoint default to
+/// English and Words
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct QueryConfig {
+    /// The vocabulary list of the query
+    pub vocabulary: Option<Vocabulary>,
+    /// The endpoint of the query
+    pub endpoint: Option<EndPoint>,
+    /// See [means_like()](RequestBuilder::means_like)
+    pub means_like: Option<String>,
+    /// See [sounds_like()](RequestBuilder::sounds_like)
+    pub sounds_like: Option<String>,
+    /// See [spelled_like()](RequestBuilder::spelled_like)
+    pub spelled_like: Option<String>,
+    /// See [related()](RequestBuilder::related)
+    pub related: Vec<(RelatedType, String)>,
+    /// See [add_topic()](RequestBuilder::add_topic)
+    pub topics: Vec<String>,
+    /// See [left_context()](RequestBuilder::left_context)
+    pub left_context: Option<String>,
+    /// See [right_context()](RequestBuilder::right_context)
+    pub right_context: Option<String>,
+    /// See [max_results()](RequestBuilder::max_results)
+    pub max_results: Option<u16>,
+    /// See [meta_data()](RequestBuilder::meta_data)
+    pub meta_data: Vec<MetaDataFlag>,
+    /// See [hint_string()](RequestBuilder::hint_string)
+    pub hint_string: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 struct RelatedTypeHolder {
     related_type: RelatedType,
@@ -128,6 +232,8 @@ enum Parameter {
     MeansLike(String),
     SoundsLike(String),
     SpelledLike(String),
+    SpelledExactly(String), //Sent as spelled like, but must not contain wildcards
+    SpelledLength(String, usize), //Sent as spelled like after expanding the pattern to the length
     Related(RelatedTypeHolder),
     Topics(Vec<String>),
     LeftContext(String),
@@ -137,6 +243,13 @@ enum Parameter {
     HintString(String), //Only supported for sug endpoint
 }
 
+#[derive(Clone, Debug)]
+enum ResultFilter {
+    MaxFrequency(f64),
+    FrequencyBand(f64, f64),
+    ExcludeWord(String),
+}
+
 impl<'a> RequestBuilder<'a> {
     /// Sets a query parameter for words which have a similar meaning to the given word
     pub fn means_like(mut self, word: &str) -> Self {
@@ -164,6 +277,31 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// Sets a query parameter for exactly the given word, e.g. to check whether it exists or to
+    /// retrieve its meta data. Unlike spelled_like() the word must not contain any wildcard
+    /// characters ('?', '*', '#' or '@'), otherwise building the request returns an
+    /// [Error::ParameterError](Error::ParameterError). This prevents a stray wildcard from
+    /// broadening the query
+    pub fn spelled_exactly(mut self, word: &str) -> Self {
+        self.parameters
+            .push(Parameter::SpelledExactly(String::from(word)));
+
+        self
+    }
+
+    /// Sets a spelled_like() query parameter for words of exactly the given length, e.g. for
+    /// crossword puzzles. Each character of the pattern other than '*' stands for one letter
+    /// (including the wildcards '?', '#' and '@'). A single '*' in the pattern is replaced by as
+    /// many '?' as are needed to reach the length, so `spelled_like_length("b*", 4)` is sent as
+    /// "b???". Building the request returns an [Error::ParameterError](Error::ParameterError)
+    /// if the length is 0, the pattern cannot have the given length or it contains more than one '*'
+    pub fn spelled_like_length(mut self, pattern: &str, exact_len: usize) -> Self {
+        self.parameters
+            .push(Parameter::SpelledLength(String::from(pattern), exact_len));
+
+        self
+    }
+
     /// Sets a query parameter for words which are related to the given word.
     /// The various options for relations are given in the [RelatedType](RelatedType) enum.
     /// See its documentation for more information on the options.
@@ -177,16 +315,51 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// Sets query parameters for words which are typically found directly before as well as
+    /// directly after the given word. This is the same as calling related() with both
+    /// [Predecessor](RelatedType::Predecessor) and [Follower](RelatedType::Follower)
+    pub fn collocation_window(self, word: &str) -> Self {
+        self.related(RelatedType::Predecessor, word)
+            .related(RelatedType::Follower, word)
+    }
+
+    /// Sets a query parameter for each of the given relations and words in order. This is the same
+    /// as calling related() for each pair
+    pub fn related_all(self, rels: &[(RelatedType, &str)]) -> Self {
+        rels.iter().fold(self, |builder, (rel_type, word)| {
+            builder.related(*rel_type, word)
+        })
+    }
+
     /// Sets a query parameter for words which fall under the topic of the given word.
     /// Multiple topics can be specified at once, however requests are limited to five
-    /// topics and as such any specified over this limit will be ignored
+    /// topics and as such building a request with more topics returns an
+    /// [Error::ParameterError](Error::ParameterError)
     pub fn add_topic(mut self, word: &str) -> Self {
         self.topics.push(String::from(word));
 
         self
     }
 
-    /// Sets a query parameter to refer to the word directly before the main query term
+    /// Sets the left and/or right context of the main query term in one call, see
+    /// [left_context()](RequestBuilder::left_context) and
+    /// [right_context()](RequestBuilder::right_context). A context which is None is not set
+    pub fn context(self, left: Option<&str>, right: Option<&str>) -> Self {
+        let builder = match left {
+            Some(word) => self.left_context(word),
+            None => self,
+        };
+
+        match right {
+            Some(word) => builder.right_context(word),
+            None => builder,
+        }
+    }
+
+    /// Sets a query parameter to refer to the word directly before the main query term.
+    /// Building the request returns an [Error::ParameterError](Error::ParameterError) if there
+    /// is no main query term (means_like(), sounds_like() or spelled_like()) and no
+    /// [Follower](RelatedType::Follower) or [Predecessor](RelatedType::Predecessor) relation
     pub fn left_context(mut self, word: &str) -> Self {
         self.parameters
             .push(Parameter::LeftContext(String::from(word)));
@@ -194,7 +367,10 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
-    /// Sets a query parameter to refer to the word directly after the main query term
+    /// Sets a query parameter to refer to the word directly after the main query term.
+    /// Building the request returns an [Error::ParameterError](Error::ParameterError) if there
+    /// is no main query term (means_like(), sounds_like() or spelled_like()) and no
+    /// [Follower](RelatedType::Follower) or [Predecessor](RelatedType::Predecessor) relation
     pub fn right_context(mut self, word: &str) -> Self {
         self.parameters
             .push(Parameter::RightContext(String::from(word)));
@@ -203,8 +379,9 @@ impl<'a> RequestBuilder<'a> {
     }
 
     /// The maximum number of results that should be returned. By default this is set to 100
-    /// and it can be increased to a maximum of 1000. This parameter is also **allowed** for the
-    /// "suggest" endpoint
+    /// and it can be increased to a maximum of 1000. Building the request returns an
+    /// [Error::ParameterError](Error::ParameterError) if the value is not between 1 and 1000.
+    /// This parameter is also **allowed** for the "suggest" endpoint
     pub fn max_results(mut self, maximum: u16) -> Self {
         self.parameters.push(Parameter::MaxResults(maximum));
 
@@ -213,13 +390,43 @@ impl<'a> RequestBuilder<'a> {
 
     /// Sets a metadata flag to specify data returned with each word.
     /// The various options for flags are given in the [MetaDataFlag](MetaDataFlag) enum.
-    /// See its documentation for more information on the options
+    /// See its documentation for more information on the options. Metadata flags can be used
+    /// with both the "words" and the "suggest" endpoint
     pub fn meta_data(mut self, flag: MetaDataFlag) -> Self {
         self.meta_data_flags.push(flag);
 
         self
     }
 
+    /// Only keeps words which are used less than the given number of times per 1,000,000 words
+    /// when the request is sent with list(). This sets the [WordFrequency](MetaDataFlag::WordFrequency)
+    /// flag so that the frequencies are known. Words without a frequency are also left out
+    pub fn unusual_words_only(mut self, max_frequency: f64) -> Self {
+        self.filters.push(ResultFilter::MaxFrequency(max_frequency));
+
+        self.with_frequency_flag()
+    }
+
+    /// Only keeps words which are used between min and max (inclusive) times per 1,000,000 words
+    /// when the request is sent with list(). This sets the [WordFrequency](MetaDataFlag::WordFrequency)
+    /// flag so that the frequencies are known. Words without a frequency are also left out.
+    /// Building the request returns an error if min is greater than max
+    pub fn frequency_band(mut self, min: f64, max: f64) -> Self {
+        self.filters.push(ResultFilter::FrequencyBand(min, max));
+
+        self.with_frequency_flag()
+    }
+
+    /// Leaves out any result which is the given word (ignoring case) when the request is sent
+    /// with list(). This is useful for related queries such as rhymes or synonyms, whose results
+    /// may include the word the query is based on
+    pub fn exclude_seed(mut self, word: &str) -> Self {
+        self.filters
+            .push(ResultFilter::ExcludeWord(word.to_lowercase()));
+
+        self
+    }
+
     /// Sets the hint string for the "suggest" endpoint. Note that this is
     /// **not allowed** for the "words" endpoint
     pub fn hint_string(mut self, hint: &str) -> Self {
@@ -229,18 +436,156 @@ impl<'a> RequestBuilder<'a> {
         self
     }
 
+    /// Adds a query parameter with the given key and value to the url exactly as given. This can be
+    /// used for parameters of the api which are not supported by this library yet. These parameters
+    /// are added after all others and are **not validated** for the vocabulary or endpoint, so
+    /// making sure that the api accepts them is up to the caller
+    pub fn raw_param(mut self, key: &str, value: &str) -> Self {
+        self.raw_params
+            .push((String::from(key), String::from(value)));
+
+        self
+    }
+
+    /// Skips the check that the parameters are supported by the vocabulary when building the
+    /// request. By default using [related()](RequestBuilder::related) with the Spanish vocabulary
+    /// returns an error, however the api has been adding support for some relations. This allows
+    /// such queries to be sent anyway, making sure that the api accepts them is up to the caller.
+    /// This takes precedence over [strict_vocabulary()](RequestBuilder::strict_vocabulary)
+    pub fn allow_unverified_params(mut self) -> Self {
+        self.allow_unverified = true;
+
+        self
+    }
+
+    /// Makes building the request return an [Error::VocabularyError](Error::VocabularyError) if
+    /// a relation is used which is not supported by the vocabulary, see
+    /// [RelatedType::is_supported_by()](RelatedType::is_supported_by). Such requests are sent by
+    /// default, but usually return no results. This check is skipped if
+    /// [allow_unverified_params()](RequestBuilder::allow_unverified_params) is also set
+    pub fn strict_vocabulary(mut self) -> Self {
+        self.strict_vocabulary = true;
+
+        self
+    }
+
+    /// Sets a timeout for the request, which applies from when it is sent until the response
+    /// has been read. If the timeout elapses, sending the request returns an
+    /// [Error::ReqwestError](Error::ReqwestError). By default there is no timeout
+    pub fn timeout(mut self, duration: Duration) -> Self {
+        self.timeout = Some(duration);
+
+        self
+    }
+
+    /// Asks the api to add the word given to the chosen parameter as the first result (the "qe"
+    /// parameter). Together with meta data flags this can be used to look up information about the
+    /// word itself, e.g. the frequency of the word given to spelled_like(). Building the request
+    /// returns an error if the chosen parameter was not set
+    pub fn query_echo(mut self, which: QueryEcho) -> Self {
+        self.query_echo = Some(which);
+
+        self
+    }
+
+    /// Sets how many times the request is sent in total if the api responds with a status of
+    /// 429 (too many requests) or 5xx (server error). Between attempts the request waits for
+    /// an increasing amount of time, starting at 100 milliseconds and doubling after every attempt.
+    /// If the last attempt also fails, sending the request with send() returns an
+    /// [Error::RetriesExhausted](Error::RetriesExhausted). By default the request is only sent once.
+    /// The waiting uses the timer of tokio 0.2, so requests with more than one attempt must be
+    /// sent from within a tokio 0.2 runtime which has the timer enabled
+    pub fn retry(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+
+        self
+    }
+
+    /// Returns whether the query will likely return no results. This is only a heuristic based
+    /// on the parameters and cannot know the actual results without sending the request.
+    /// A query is considered likely to be empty if
+    /// - no parameters which select words are set (e.g. only metadata flags),
+    /// - the maximum number of results is 0,
+    /// - a spelled_like pattern without wildcards is combined with any other constraint
+    ///   (it only matches that exact word, which rarely also fits the other constraint)
+    pub fn likely_empty(&self) -> bool {
+        let mut has_constraint = !self.topics.is_empty();
+        let mut has_literal_spelling = false;
+        let mut other_constraints = 0;
+
+        for param in &self.parameters {
+            match param {
+                Parameter::MaxResults(0) => return true,
+                Parameter::MaxResults(_) | Parameter::MetaData(_) => (),
+                Parameter::SpelledLike(pattern) => {
+                    has_constraint = true;
+                    if !pattern.contains(SPELLING_WILDCARDS) {
+                        has_literal_spelling = true;
+                    }
+                }
+                Parameter::SpelledLength(_, _) => has_constraint = true,
+                Parameter::SpelledExactly(_) => {
+                    has_constraint = true;
+                    has_literal_spelling = true;
+                }
+                Parameter::LeftContext(_) | Parameter::RightContext(_) => has_constraint = true,
+                _ => {
+                    has_constraint = true;
+                    other_constraints += 1;
+                }
+            }
+        }
+
+        !has_constraint || (has_literal_spelling && other_constraints > 0)
+    }
+
     /// Converts the RequestBuilder into a Request which can be executed by calling the send()
     /// method on it. This method will return an error if any of the given parameters have not been
     /// used correctly or the underlying call to reqwest to build the request fails
-    pub fn build(&self) -> Result<Request> {
+    pub fn build(&self) -> Result<Request<'a>> {
+        for filter in &self.filters {
+            filter.validate()?;
+        }
+
+        let has_context = self.parameters.iter().any(|param| {
+            matches!(
+                param,
+                Parameter::LeftContext(_) | Parameter::RightContext(_)
+            )
+        });
+        if has_context && !self.parameters.iter().any(Parameter::is_context_anchor) {
+            return Err(Error::ParameterError(String::from(
+                "A context requires a main query term or a Follower or Predecessor relation",
+            )));
+        }
+
+        if self.strict_vocabulary && !self.allow_unverified {
+            for param in &self.parameters {
+                if let Parameter::Related(holder) = param {
+                    if !holder.related_type.is_supported_by(self.vocabulary) {
+                        return Err(Error::VocabularyError((
+                            format!("{:?}", self.vocabulary),
+                            format!("Related ({:?})", holder.related_type),
+                        )));
+                    }
+                }
+            }
+        }
+
         let mut params_list: Vec<(String, String)> = Vec::new();
         let mut parameters = self.parameters.clone();
 
-        if self.topics.len() > 0 {
-            parameters.push(Parameter::Topics(self.topics.clone()));
+        if !self.topics.is_empty() {
+            //Each topic is normalized on its own as the rules do not apply within the joined value
+            let topics = self
+                .topics
+                .iter()
+                .map(|topic| self.client.normalization.apply(topic))
+                .collect();
+            parameters.push(Parameter::Topics(topics));
         }
 
-        if self.meta_data_flags.len() > 0 {
+        if !self.meta_data_flags.is_empty() {
             parameters.push(Parameter::MetaData(self.meta_data_flags.clone()));
 
             for flag in self.meta_data_flags.clone() {
@@ -256,22 +601,52 @@ impl<'a> RequestBuilder<'a> {
         }
 
         for param in parameters {
-            params_list.push(param.build(&self.vocabulary, &self.endpoint)?);
+            let (key, mut value) =
+                param.build(&self.vocabulary, &self.endpoint, self.allow_unverified)?;
+            if param.is_normalized() {
+                value = self.client.normalization.apply(&value);
+            }
+
+            params_list.push((key, value));
+        }
+
+        if let Some(echo) = self.query_echo {
+            let identifier = echo.get_identifier();
+            if !params_list.iter().any(|(key, _)| key == identifier) {
+                return Err(Error::ParameterError(format!(
+                    "The parameter {} cannot be echoed as it is not set",
+                    identifier
+                )));
+            }
+
+            params_list.push((String::from("qe"), String::from(identifier)));
+        }
+        params_list.extend(self.raw_params.iter().cloned());
+
+        let mut request = self.client.client.get(&format!(
+            "{}{}",
+            self.client.base_url,
+            self.endpoint.get_string()
+        ));
+        for (name, value) in &self.client.headers {
+            request = request.header(name, value.as_str());
         }
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        let request = request.query(&params_list).build()?;
 
-        let request = self
-            .client
-            .client
-            .get(&format!(
-                "https://api.datamuse.com/{}",
-                self.endpoint.get_string()
-            ))
-            .query(&params_list)
-            .build()?;
+        if self.client.require_https && request.url().scheme() != "https" {
+            return Err(Error::InsecureUrl(request.url().to_string()));
+        }
 
         Ok(Request {
             request,
-            client: &self.client.client,
+            client: self.client,
+            timeout: self.timeout,
+            max_attempts: self.max_attempts,
+            allow_unverified: self.allow_unverified,
+            strict_vocabulary: self.strict_vocabulary,
         })
     }
 
@@ -283,7 +658,162 @@ impl<'a> RequestBuilder<'a> {
 
     /// A convenience method to build and send the request as well as parse the json in one step
     pub async fn list(&self) -> Result<Vec<WordElement>> {
-        self.send().await?.list()
+        Ok(self.apply_filters(self.send().await?.list()?))
+    }
+
+    /// A convenience method to send the request and return only the best matching word,
+    /// see [best()](Response::best). Filters such as
+    /// [unusual_words_only()](RequestBuilder::unusual_words_only) are applied first
+    pub async fn best(&self) -> Result<Option<WordElement>> {
+        Ok(self.list().await?.into_iter().next())
+    }
+
+    /// A convenience method to send the request and return only the words themselves without
+    /// their scores or metadata, see [words()](Response::words). Filters such as
+    /// [unusual_words_only()](RequestBuilder::unusual_words_only) are applied first
+    pub async fn words_only(&self) -> Result<Vec<String>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .map(|elem| elem.word)
+            .collect())
+    }
+
+    /// A convenience method to send the request and order the resulting word list as specified
+    /// by the given [SuggestSort](SuggestSort). This is intended for the "suggest" endpoint
+    /// but can be used with any request
+    pub async fn suggestions_sorted(&self, sort: SuggestSort) -> Result<Vec<WordElement>> {
+        let mut word_list = self.list().await?;
+
+        match sort {
+            SuggestSort::ApiOrder => (),
+            SuggestSort::Alphabetical => word_list.sort_by(|a, b| a.word.cmp(&b.word)),
+            SuggestSort::ByLength => word_list.sort_by_key(|elem| elem.word.chars().count()),
+        }
+
+        Ok(word_list)
+    }
+
+    /// A convenience method to send the request with the [WordFrequency](MetaDataFlag::WordFrequency)
+    /// flag set and return the median frequency of the resulting words. Words without a frequency
+    /// are left out. This returns None if no word has a frequency. The median is an f64 rather than
+    /// an f32 since [frequency](WordElement::frequency) is parsed as an f64
+    pub async fn median_frequency(&self) -> Result<Option<f64>> {
+        let frequencies = self
+            .clone()
+            .with_frequency_flag()
+            .list()
+            .await?
+            .into_iter()
+            .filter_map(|elem| elem.frequency)
+            .collect();
+
+        Ok(median(frequencies))
+    }
+
+    /// A convenience method to send the request and label each word of the resulting list
+    /// with the given source label
+    pub async fn list_sourced(&self, label: &str) -> Result<Vec<SourcedWord>> {
+        Ok(self
+            .list()
+            .await?
+            .into_iter()
+            .map(|element| SourcedWord {
+                element,
+                source: String::from(label),
+            })
+            .collect())
+    }
+
+    /// Returns a stream of pages of results with up to page_size new words each. As the api does not
+    /// support pagination, each page is requested by sending the query again with a maximum number of
+    /// results increased by page_size and leaving out the words which were already returned. Since the
+    /// api returns at most 1000 results, the stream ends after at most 1000 words (or earlier once no
+    /// new words are returned). Pages which contain no words after applying filters such as
+    /// [unusual_words_only()](RequestBuilder::unusual_words_only) are skipped. The stream also
+    /// ends after returning an error
+    pub fn paged(self, page_size: u16) -> impl Stream<Item = Result<Vec<WordElement>>> + 'a {
+        let state = (self, HashSet::new(), 0_u16, page_size == 0);
+
+        stream::unfold(
+            state,
+            move |(mut builder, mut seen, mut max, mut done)| async move {
+                //Pages which are empty after applying the filters are skipped
+                while !done {
+                    max = max.saturating_add(page_size).min(MAX_RESULTS_LIMIT);
+                    builder
+                        .parameters
+                        .retain(|param| !matches!(param, Parameter::MaxResults(_)));
+                    builder.parameters.push(Parameter::MaxResults(max));
+
+                    let word_list = match builder.send().await.and_then(|response| response.list())
+                    {
+                        Ok(word_list) => word_list,
+                        Err(err) => return Some((Err(err), (builder, seen, max, true))),
+                    };
+                    done = word_list.len() < usize::from(max) || max == MAX_RESULTS_LIMIT;
+
+                    let new_words: Vec<WordElement> = word_list
+                        .into_iter()
+                        .filter(|elem| seen.insert(elem.word.clone()))
+                        .collect();
+                    if new_words.is_empty() {
+                        return None;
+                    }
+
+                    let page = builder.apply_filters(new_words);
+                    if !page.is_empty() {
+                        return Some((Ok(page), (builder, seen, max, done)));
+                    }
+                }
+
+                None
+            },
+        )
+    }
+
+    /// Sends the request without the [Definitions](MetaDataFlag::Definitions) flag and then
+    /// fetches the definitions for only the first k words with one additional request per word.
+    /// This reduces the size of the responses when only the top few words need definitions.
+    /// All other words in the returned list have no definitions
+    pub async fn list_with_top_definitions(&self, k: usize) -> Result<Vec<WordElement>> {
+        let mut without_definitions = self.clone();
+        without_definitions
+            .meta_data_flags
+            .retain(|flag| !matches!(flag, MetaDataFlag::Definitions));
+
+        let mut word_list = without_definitions.list().await?;
+
+        let definition_queries = word_list.iter().take(k).map(|elem| async move {
+            //Options such as the timeout, retries and raw params carry over to the follow-up query
+            let mut query = self.clone();
+            query.endpoint = EndPoint::Words;
+            query.parameters.clear();
+            query.topics.clear();
+            query.meta_data_flags.clear();
+            query.filters.clear();
+            query.query_echo = None;
+
+            let definitions = query
+                .spelled_like(&elem.word)
+                .meta_data(MetaDataFlag::Definitions)
+                .max_results(1)
+                .list()
+                .await?
+                .into_iter()
+                .find(|def_elem| def_elem.word == elem.word)
+                .and_then(|def_elem| def_elem.definitions);
+
+            Ok::<_, Error>(definitions)
+        });
+        let definitions = future::try_join_all(definition_queries).await?;
+
+        for (elem, definitions) in word_list.iter_mut().zip(definitions) {
+            elem.definitions = definitions;
+        }
+
+        Ok(word_list)
     }
 
     pub(crate) fn new(
@@ -298,22 +828,350 @@ impl<'a> RequestBuilder<'a> {
             parameters: Vec::new(),
             topics: Vec::new(),
             meta_data_flags: Vec::new(),
+            filters: Vec::new(),
+            timeout: None,
+            max_attempts: 1,
+            raw_params: Vec::new(),
+            allow_unverified: false,
+            query_echo: None,
+            strict_vocabulary: false,
+        }
+    }
+
+    pub(crate) fn from_config(client: &'a DatamuseClient, config: QueryConfig) -> Self {
+        let mut builder = RequestBuilder::new(
+            client,
+            config.vocabulary.unwrap_or(Vocabulary::English),
+            config.endpoint.unwrap_or(EndPoint::Words),
+        );
+
+        if let Some(word) = config.means_like {
+            builder = builder.means_like(&word);
+        }
+        if let Some(word) = config.sounds_like {
+            builder = builder.sounds_like(&word);
+        }
+        if let Some(word) = config.spelled_like {
+            builder = builder.spelled_like(&word);
+        }
+        for (rel_type, word) in config.related {
+            builder = builder.related(rel_type, &word);
+        }
+        for topic in config.topics {
+            builder = builder.add_topic(&topic);
+        }
+        if let Some(word) = config.left_context {
+            builder = builder.left_context(&word);
+        }
+        if let Some(word) = config.right_context {
+            builder = builder.right_context(&word);
+        }
+        if let Some(maximum) = config.max_results {
+            builder = builder.max_results(maximum);
         }
+        for flag in config.meta_data {
+            builder = builder.meta_data(flag);
+        }
+        if let Some(hint) = config.hint_string {
+            builder = builder.hint_string(&hint);
+        }
+
+        builder
+    }
+
+    fn from_url(client: &'a DatamuseClient, url: &reqwest::Url) -> Result<Self> {
+        let endpoint = match url.path().rsplit('/').next() {
+            Some("words") => EndPoint::Words,
+            Some("sug") => EndPoint::Suggest,
+            _ => {
+                return Err(Error::ParameterError(format!(
+                    "Unknown endpoint in {}",
+                    url.path()
+                )))
+            }
+        };
+
+        let mut ipa = false;
+        let mut vocabulary = Vocabulary::English;
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "ipa" => ipa = value == "1",
+                "v" => {
+                    vocabulary = match value.as_ref() {
+                        "es" => Vocabulary::Spanish,
+                        "enwiki" => Vocabulary::EnglishWiki,
+                        _ => {
+                            return Err(Error::ParameterError(format!(
+                                "Unknown vocabulary {}",
+                                value
+                            )))
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        let mut builder = RequestBuilder::new(client, vocabulary, endpoint);
+        for (key, value) in url.query_pairs() {
+            let parameter = match key.as_ref() {
+                "ipa" | "v" => continue,
+                "ml" => Parameter::MeansLike(value.into_owned()),
+                "sl" => Parameter::SoundsLike(value.into_owned()),
+                "sp" => Parameter::SpelledLike(value.into_owned()),
+                "lc" => Parameter::LeftContext(value.into_owned()),
+                "rc" => Parameter::RightContext(value.into_owned()),
+                "s" => Parameter::HintString(value.into_owned()),
+                "max" => match value.parse() {
+                    Ok(maximum) => Parameter::MaxResults(maximum),
+                    Err(_) => {
+                        return Err(Error::ParameterError(format!(
+                            "Invalid value {} for max",
+                            value
+                        )))
+                    }
+                },
+                "qe" => match QueryEcho::from_identifier(&value) {
+                    Some(echo) => {
+                        builder.query_echo = Some(echo);
+                        continue;
+                    }
+                    None => {
+                        return Err(Error::ParameterError(format!(
+                            "Unknown query echo {}",
+                            value
+                        )))
+                    }
+                },
+                "topics" => {
+                    builder.topics.extend(value.split(',').map(String::from));
+                    continue;
+                }
+                "md" => {
+                    for letter in value.chars() {
+                        match MetaDataFlag::from_letter_identifier(letter, ipa) {
+                            Some(flag) => builder.meta_data_flags.push(flag),
+                            None => {
+                                return Err(Error::ParameterError(format!(
+                                    "Unknown meta data flag {}",
+                                    letter
+                                )))
+                            }
+                        }
+                    }
+                    continue;
+                }
+                key => match key
+                    .strip_prefix("rel_")
+                    .and_then(RelatedType::from_type_identifier)
+                {
+                    Some(related_type) => Parameter::Related(RelatedTypeHolder {
+                        related_type,
+                        value: value.into_owned(),
+                    }),
+                    None => {
+                        builder
+                            .raw_params
+                            .push((String::from(key), value.into_owned()));
+                        continue;
+                    }
+                },
+            };
+
+            builder.parameters.push(parameter);
+        }
+
+        Ok(builder)
+    }
+
+    pub(crate) fn with_frequency_flag(self) -> Self {
+        if self
+            .meta_data_flags
+            .iter()
+            .any(|flag| matches!(flag, MetaDataFlag::WordFrequency))
+        {
+            self
+        } else {
+            self.meta_data(MetaDataFlag::WordFrequency)
+        }
+    }
+
+    pub(crate) fn apply_filters(&self, mut word_list: Vec<WordElement>) -> Vec<WordElement> {
+        for filter in &self.filters {
+            word_list.retain(|elem| filter.keep(elem));
+        }
+
+        word_list
     }
 }
 
 impl<'a> Request<'a> {
+    /// Converts the request back into a [RequestBuilder](RequestBuilder) by parsing the query
+    /// parameters of its url. This allows a built request to be modified before it is sent.
+    /// Note that options which are only applied after receiving the response (such as
+    /// [unusual_words_only()](RequestBuilder::unusual_words_only)) are not part of the url and
+    /// are lost. Parameters unknown to this library are kept as they are, see
+    /// [raw_param()](RequestBuilder::raw_param). The timeout, retries and the
+    /// [allow_unverified_params()](RequestBuilder::allow_unverified_params) and
+    /// [strict_vocabulary()](RequestBuilder::strict_vocabulary) options are kept as well.
+    /// No client needs to be given since the request already holds the client it was built with.
+    /// An error is returned if the value of a known parameter cannot be parsed, e.g. an unknown
+    /// vocabulary
+    pub fn into_builder(self) -> Result<RequestBuilder<'a>> {
+        let mut builder = RequestBuilder::from_url(self.client, self.request.url())?;
+        builder.timeout = self.timeout;
+        builder.max_attempts = self.max_attempts;
+        builder.allow_unverified = self.allow_unverified;
+        builder.strict_vocabulary = self.strict_vocabulary;
+
+        Ok(builder)
+    }
+
+    /// Returns the url the request will be sent to including all of its query parameters.
+    /// This can be used to log requests or to compare the results in a browser
+    pub fn url(&self) -> &reqwest::Url {
+        self.request.url()
+    }
+
+    /// Returns a curl command which sends the same request including its method and headers.
+    /// The url and header values are quoted so the command can be copied into a shell, which
+    /// is useful when sharing a query in a bug report
+    pub fn as_curl(&self) -> String {
+        let mut command = format!(
+            "curl -X {} {}",
+            self.request.method(),
+            shell_quote(self.request.url().as_str())
+        );
+        for (name, value) in self.request.headers() {
+            let header = format!("{}: {}", name, String::from_utf8_lossy(value.as_bytes()));
+            command.push_str(" -H ");
+            command.push_str(&shell_quote(&header));
+        }
+
+        command
+    }
+
     /// Sends the built request and returns the response. This response can later be parsed with its
-    /// list() method
+    /// list() method. If the api responds with a status other than 2xx, an
+    /// [Error::HttpStatus](Error::HttpStatus) containing the status and body is returned
     pub async fn send(self) -> Result<Response> {
-        let json = self.client.execute(self.request).await?.text().await?;
-        Ok(Response::new(json))
+        let client = self.client;
+        let max_attempts = self.max_attempts;
+        let response = self.execute().await?;
+        let status = response.status();
+        let url = response.url().to_string();
+        if max_attempts > 1 && is_retryable(status) {
+            return Err(Error::RetriesExhausted(max_attempts, status));
+        }
+
+        let json = match client.max_response_bytes {
+            Some(limit) => read_limited(response, limit).await?,
+            None => response.text().await?,
+        };
+        if !status.is_success() {
+            return Err(Error::HttpStatus(status, json));
+        }
+        Ok(Response::new(json).with_url(url))
+    }
+
+    /// Sends the built request and returns the underlying reqwest response without reading its
+    /// body. This gives full access to the status, headers and body of the response, however
+    /// the caller is responsible for checking the status and parsing the body. Options of the
+    /// client which concern the body (such as the maximum response size) are not applied.
+    /// If retries are enabled, the response of the last attempt is returned
+    pub async fn send_raw(self) -> Result<reqwest::Response> {
+        self.execute().await
+    }
+
+    async fn execute(self) -> Result<reqwest::Response> {
+        let mut delay = RETRY_BASE_DELAY;
+
+        for _ in 1..self.max_attempts {
+            let request = match self.request.try_clone() {
+                Some(request) => request,
+                None => break, //Only requests with streamed bodies cannot be cloned
+            };
+
+            self.client.count_request();
+            let response = self.client.client.execute(request).await?;
+            if !is_retryable(response.status()) {
+                return Ok(response);
+            }
+
+            tokio::time::delay_for(delay).await;
+            delay *= 2;
+        }
+
+        self.client.count_request();
+        Ok(self.client.client.execute(self.request).await?)
+    }
+}
+
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let middle = values.len() / 2;
+    if values.len() % 2 == 1 {
+        Some(values[middle])
+    } else {
+        Some((values[middle - 1] + values[middle]) / 2.0)
+    }
+}
+
+//Replaces the '*' of a spelled like pattern with '?' so that it only matches words of the length
+fn expand_to_length(pattern: &str, exact_len: usize) -> Result<String> {
+    let letters = pattern.chars().filter(|c| *c != '*').count();
+    let expanded = match pattern.matches('*').count() {
+        _ if exact_len == 0 => None, //Would be sent as an empty spelled like parameter
+        0 if letters == exact_len => Some(String::from(pattern)),
+        1 if letters <= exact_len => {
+            Some(pattern.replacen('*', &"?".repeat(exact_len - letters), 1))
+        }
+        _ => None,
+    };
+
+    expanded.ok_or_else(|| {
+        Error::ParameterError(format!(
+            "The pattern {} cannot be expanded to a length of {}",
+            pattern, exact_len
+        ))
+    })
+}
+
+//Wraps the value in single quotes so a shell does not interpret any of its characters
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+pub(crate) fn is_retryable(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+//Reads the body chunk by chunk so that reading stops as soon as the limit is exceeded
+async fn read_limited(mut response: reqwest::Response, limit: usize) -> Result<String> {
+    let mut body: Vec<u8> = Vec::new();
+
+    while let Some(chunk) = response.chunk().await? {
+        if body.len() + chunk.len() > limit {
+            return Err(Error::ResponseTooLarge(limit));
+        }
+        body.extend_from_slice(&chunk);
     }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
 }
 
 impl Parameter {
-    fn build(&self, vocab: &Vocabulary, endpoint: &EndPoint) -> Result<(String, String)> {
-        if let Parameter::Related(_) = self {
+    fn build(
+        &self,
+        vocab: &Vocabulary,
+        endpoint: &EndPoint,
+        allow_unverified: bool,
+    ) -> Result<(String, String)> {
+        if let (Parameter::Related(_), false) = (self, allow_unverified) {
             //Error for using related with spanish vocabulary
             if let Vocabulary::Spanish = vocab {
                 return Err(Error::VocabularyError((
@@ -323,6 +1181,44 @@ impl Parameter {
             }
         }
 
+        if let Parameter::SpelledExactly(word) = self {
+            if word.contains(SPELLING_WILDCARDS) {
+                return Err(Error::ParameterError(format!(
+                    "The exact spelling {} contains a wildcard character",
+                    word
+                )));
+            }
+        }
+
+        let word = match self {
+            Self::MeansLike(val)
+            | Self::SoundsLike(val)
+            | Self::SpelledLike(val)
+            | Self::SpelledExactly(val)
+            | Self::HintString(val) => Some(val.as_str()),
+            Self::Related(val) => Some(val.value.as_str()),
+            _ => None,
+        };
+        let is_empty = match self {
+            Self::Topics(topic_list) => topic_list.iter().any(|topic| topic.trim().is_empty()),
+            _ => matches!(word, Some(word) if word.trim().is_empty()),
+        };
+        if is_empty {
+            return Err(Error::ParameterError(format!(
+                "The value of the parameter {} is empty",
+                self
+            )));
+        }
+
+        if let Parameter::MaxResults(max) = self {
+            if *max == 0 || *max > MAX_RESULTS_LIMIT {
+                return Err(Error::ParameterError(format!(
+                    "The maximum number of results {} is not between 1 and {}",
+                    max, MAX_RESULTS_LIMIT
+                )));
+            }
+        }
+
         if let EndPoint::Words = endpoint {
             //Error for using hint string for the words endpoint
             if let Parameter::HintString(_) = self {
@@ -337,6 +1233,7 @@ impl Parameter {
             match self {
                 Parameter::MaxResults(_) => (),
                 Parameter::HintString(_) => (),
+                Parameter::MetaData(_) => (),
                 val => {
                     return Err(Error::EndPointError((
                         String::from("Suggest"),
@@ -349,25 +1246,21 @@ impl Parameter {
         let param = match self {
             Self::MeansLike(val) => (String::from("ml"), val.clone()),
             Self::SoundsLike(val) => (String::from("sl"), val.clone()),
-            Self::SpelledLike(val) => (String::from("sp"), val.clone()),
+            Self::SpelledLike(val) | Self::SpelledExactly(val) => (String::from("sp"), val.clone()),
+            Self::SpelledLength(pattern, exact_len) => {
+                (String::from("sp"), expand_to_length(pattern, *exact_len)?)
+            }
             Self::Related(val) => (format!("rel_{}", val.get_type_identifier()), val.get_word()),
             Self::Topics(topic_list) => {
-                let mut topics_concat = String::from("");
-                let mut len = topic_list.len();
-
-                if len > 5 {
-                    len = 5;
-                }
-
-                let mut i = 0;
-                while i < len - 1 {
-                    topics_concat = topics_concat + &topic_list[i];
-                    topics_concat.push(',');
-                    i += 1;
+                if topic_list.len() > MAX_TOPICS {
+                    return Err(Error::ParameterError(format!(
+                        "{} topics were given, but at most {} are allowed",
+                        topic_list.len(),
+                        MAX_TOPICS
+                    )));
                 }
-                topics_concat = topics_concat + &topic_list[len - 1];
 
-                (String::from("topics"), topics_concat)
+                (String::from("topics"), topic_list.join(","))
             }
             Self::LeftContext(val) => (String::from("lc"), val.clone()),
             Self::RightContext(val) => (String::from("rc"), val.clone()),
@@ -375,7 +1268,10 @@ impl Parameter {
             Self::MetaData(flags) => {
                 let mut flags_concat = String::from("");
                 for flag in flags {
-                    flags_concat.push(flag.get_letter_identifier());
+                    let letter = flag.get_letter_identifier();
+                    if !flags_concat.contains(letter) {
+                        flags_concat.push(letter); //Each flag is only sent once
+                    }
                 }
 
                 (String::from("md"), flags_concat)
@@ -387,12 +1283,90 @@ impl Parameter {
     }
 }
 
+impl ResultFilter {
+    fn keep(&self, elem: &WordElement) -> bool {
+        match self {
+            Self::MaxFrequency(max) => matches!(elem.frequency, Some(val) if val < *max),
+            Self::FrequencyBand(min, max) => {
+                matches!(elem.frequency, Some(val) if *min <= val && val <= *max)
+            }
+            Self::ExcludeWord(word) => elem.word.to_lowercase() != *word,
+        }
+    }
+
+    fn validate(&self) -> Result<()> {
+        if let Self::FrequencyBand(min, max) = self {
+            if min > max {
+                return Err(Error::ParameterError(format!(
+                    "The frequency band minimum {} is greater than its maximum {}",
+                    min, max
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Parameter {
+    //Whether the left and right context can refer to this parameter
+    fn is_context_anchor(&self) -> bool {
+        match self {
+            Self::MeansLike(_)
+            | Self::SoundsLike(_)
+            | Self::SpelledLike(_)
+            | Self::SpelledExactly(_)
+            | Self::SpelledLength(_, _) => true,
+            Self::Related(holder) => matches!(
+                holder.related_type,
+                RelatedType::Follower | RelatedType::Predecessor
+            ),
+            _ => false,
+        }
+    }
+
+    //Whether the normalization rules of the client are applied to the value.
+    //Spelled like patterns with wildcards are left as is so the wildcards are preserved.
+    //Topics are normalized one by one before they are joined
+    fn is_normalized(&self) -> bool {
+        match self {
+            Self::SpelledLike(pattern) | Self::SpelledLength(pattern, _) => {
+                !pattern.contains(SPELLING_WILDCARDS)
+            }
+            Self::MaxResults(_) | Self::MetaData(_) | Self::Topics(_) => false,
+            _ => true,
+        }
+    }
+}
+
+impl NormalizationRules {
+    pub(crate) fn apply(&self, word: &str) -> String {
+        let mut word = String::from(word);
+
+        if self.collapse_whitespace {
+            word = word.split_whitespace().collect::<Vec<&str>>().join(" ");
+        } else if self.trim {
+            word = String::from(word.trim());
+        }
+        if self.strip_accents {
+            word = word.nfd().filter(|c| !is_combining_mark(*c)).collect();
+        }
+        if self.lowercase {
+            word = word.to_lowercase();
+        }
+
+        word
+    }
+}
+
 impl Display for Parameter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         let name = match self {
             Self::MeansLike(_) => "MeansLike",
             Self::SoundsLike(_) => "SoundsLike",
             Self::SpelledLike(_) => "SpelledLike",
+            Self::SpelledExactly(_) => "SpelledExactly",
+            Self::SpelledLength(_, _) => "SpelledLength",
             Self::Related(_) => "Related",
             Self::Topics(_) => "Topic",
             Self::LeftContext(_) => "LeftContext",
@@ -432,7 +1406,59 @@ impl RelatedTypeHolder {
     }
 }
 
+impl RelatedType {
+    /// Returns whether the api returns results for this relation with the given vocabulary.
+    /// Relations are not available for the Spanish vocabulary according to the api documentation.
+    /// No relation has been confirmed to be unsupported by the English Wikipedia vocabulary yet,
+    /// so all of them are treated as supported
+    pub fn is_supported_by(&self, vocabulary: Vocabulary) -> bool {
+        match vocabulary {
+            Vocabulary::English => true,
+            Vocabulary::Spanish => false,
+            //Relations should only be excluded here once an empty response has been observed
+            Vocabulary::EnglishWiki => true,
+        }
+    }
+
+    fn from_type_identifier(identifier: &str) -> Option<Self> {
+        let related_type = match identifier {
+            "jja" => Self::NounModifiedBy,
+            "jjb" => Self::AdjectiveModifier,
+            "syn" => Self::Synonym,
+            "trg" => Self::Trigger,
+            "ant" => Self::Antonym,
+            "spc" => Self::KindOf,
+            "gen" => Self::MoreGeneral,
+            "com" => Self::Comprises,
+            "par" => Self::PartOf,
+            "bga" => Self::Follower,
+            "bgb" => Self::Predecessor,
+            "rhy" => Self::Rhyme,
+            "nry" => Self::ApproximateRhyme,
+            "hom" => Self::Homophones,
+            "cns" => Self::ConsonantMatch,
+            _ => return None,
+        };
+
+        Some(related_type)
+    }
+}
+
 impl MetaDataFlag {
+    fn from_letter_identifier(letter: char, ipa: bool) -> Option<Self> {
+        let flag = match letter {
+            'd' => Self::Definitions,
+            'p' => Self::PartsOfSpeech,
+            's' => Self::SyllableCount,
+            'r' if ipa => Self::Pronunciation(PronunciationFormat::Ipa),
+            'r' => Self::Pronunciation(PronunciationFormat::Arpabet),
+            'f' => Self::WordFrequency,
+            _ => return None,
+        };
+
+        Some(flag)
+    }
+
     fn get_letter_identifier(&self) -> char {
         match self {
             Self::Definitions => 'd',
@@ -444,8 +1470,27 @@ impl MetaDataFlag {
     }
 }
 
-impl EndPoint {
-    fn get_string(&self) -> String {
+impl QueryEcho {
+    fn from_identifier(identifier: &str) -> Option<Self> {
+        match identifier {
+            "ml" => Some(Self::MeansLike),
+            "sl" => Some(Self::SoundsLike),
+            "sp" => Some(Self::SpelledLike),
+            _ => None,
+        }
+    }
+
+    fn get_identifier(&self) -> &'static str {
+        match self {
+            Self::MeansLike => "ml",
+            Self::SoundsLike => "sl",
+            Self::SpelledLike => "sp",
+        }
+    }
+}
+
+impl EndPoint {
+    fn get_string(&self) -> String {
         match self {
             Self::Words => String::from("words"),
             Self::Suggest => String::from("sug"),
@@ -465,9 +1510,14 @@ impl Vocabulary {
 
 #[cfg(test)]
 mod tests {
+    use super::Parameter;
+    use crate::test_server::{MockResponse, MockServer};
     use crate::{
-        DatamuseClient, EndPoint, MetaDataFlag, PronunciationFormat, RelatedType, Vocabulary,
+        DatamuseClient, EndPoint, Error, MetaDataFlag, NormalizationRules, PronunciationFormat,
+        QueryConfig, QueryEcho, RelatedType, SuggestSort, Vocabulary,
     };
+    use futures::StreamExt;
+    use std::time::Duration;
 
     #[test]
     fn means_like_and_sounds_like() {
@@ -503,10 +1553,11 @@ mod tests {
         let request = client
             .new_query(Vocabulary::English, EndPoint::Words)
             .right_context("food")
+            .means_like("tasty")
             .max_results(500);
 
         assert_eq!(
-            "https://api.datamuse.com/words?rc=food&max=500",
+            "https://api.datamuse.com/words?rc=food&ml=tasty&max=500",
             request.build().unwrap().request.url().as_str()
         );
     }
@@ -526,6 +1577,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn too_many_topics() {
+        let client = DatamuseClient::new();
+        let mut request = client.new_query(Vocabulary::English, EndPoint::Words);
+        for topic in &["sea", "boat", "wind", "sail", "wave"] {
+            request = request.add_topic(topic);
+        }
+        assert_eq!(
+            "https://api.datamuse.com/words?topics=sea%2Cboat%2Cwind%2Csail%2Cwave",
+            request.build().unwrap().url().as_str()
+        );
+
+        let request = request.add_topic("storm");
+        match request.build() {
+            Err(Error::ParameterError(_)) => (),
+            val => panic!("Expected ParameterError, got {:?}", val),
+        }
+    }
+
+    #[test]
+    fn duplicate_meta_data_flags() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .meta_data(MetaDataFlag::Definitions)
+            .meta_data(MetaDataFlag::Definitions);
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=cow&md=d",
+            request.build().unwrap().url().as_str()
+        );
+
+        let request = request
+            .meta_data(MetaDataFlag::WordFrequency)
+            .meta_data(MetaDataFlag::Definitions);
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=cow&md=df",
+            request.build().unwrap().url().as_str()
+        );
+    }
+
+    #[test]
+    fn empty_parameters() {
+        let client = DatamuseClient::new();
+        let requests = vec![
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like(""),
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like("cow")
+                .add_topic(" "),
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .related(RelatedType::Rhyme, "  "),
+            client
+                .new_query(Vocabulary::English, EndPoint::Suggest)
+                .hint_string(""),
+        ];
+
+        for request in requests {
+            match request.build() {
+                Err(Error::ParameterError(_)) => (),
+                val => panic!("Expected ParameterError, got {:?}", val),
+            }
+        }
+    }
+
     #[test]
     fn suggest_endpoint() {
         let client = DatamuseClient::new();
@@ -540,6 +1659,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn suggest_endpoint_meta_data() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("hel")
+            .meta_data(MetaDataFlag::PartsOfSpeech)
+            .meta_data(MetaDataFlag::Pronunciation(PronunciationFormat::Ipa));
+
+        assert_eq!(
+            "https://api.datamuse.com/sug?ipa=1&s=hel&md=pr",
+            request.build().unwrap().request.url().as_str()
+        );
+    }
+
     #[test]
     #[should_panic]
     fn suggest_endpoint_fail() {
@@ -703,4 +1837,1207 @@ mod tests {
             request.build().unwrap().request.url().as_str()
         );
     }
+
+    #[test]
+    fn collocation_window() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .collocation_window("coffee");
+
+        assert_eq!(
+            "https://api.datamuse.com/words?rel_bgb=coffee&rel_bga=coffee",
+            request.build().unwrap().request.url().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn list_with_top_definitions() {
+        let server = MockServer::routes(vec![
+            (
+                "ml=dairy",
+                MockResponse::json(
+                    r#"[{"word":"milk","score":300},{"word":"cheese","score":200},{"word":"butter","score":100}]"#,
+                ),
+            ),
+            (
+                "sp=milk",
+                MockResponse::json(
+                    r#"[{"word":"milk","score":100,"defs":["n\ta white nutritious liquid"]}]"#,
+                ),
+            ),
+            (
+                "sp=cheese",
+                MockResponse::json(
+                    r#"[{"word":"cheese","score":100,"defs":["n\ta solid food made from milk"]}]"#,
+                ),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .meta_data(MetaDataFlag::Definitions)
+            .list_with_top_definitions(2)
+            .await
+            .unwrap();
+
+        assert_eq!(3, server.hits());
+        assert_eq!(3, word_list.len());
+        assert_eq!(
+            "a white nutritious liquid",
+            word_list[0].definitions.as_ref().unwrap()[0].definition
+        );
+        assert_eq!(
+            "a solid food made from milk",
+            word_list[1].definitions.as_ref().unwrap()[0].definition
+        );
+        assert_eq!(None, word_list[2].definitions);
+    }
+
+    #[tokio::test]
+    async fn list_with_top_definitions_options() {
+        let server = MockServer::routes(vec![(
+            "ml=dairy",
+            MockResponse::json(r#"[{"word":"milk","score":300}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .add_topic("farm")
+            .raw_param("foo", "bar")
+            .list_with_top_definitions(1)
+            .await
+            .unwrap();
+
+        let request_line = server.last_request();
+        assert!(request_line.contains("sp=milk&max=1&md=d&foo=bar"));
+        assert!(!request_line.contains("topics"));
+    }
+
+    #[test]
+    fn query_from_config() {
+        let config: QueryConfig = serde_json::from_str(
+            r#"{
+                "vocabulary": "EnglishWiki",
+                "means_like": "ocean",
+                "related": [["Rhyme", "sea"]],
+                "topics": ["water", "boat"],
+                "max_results": 20,
+                "meta_data": ["PartsOfSpeech", {"Pronunciation": "Ipa"}]
+            }"#,
+        )
+        .unwrap();
+        let client = DatamuseClient::new();
+        let request = client.query_from_config(config);
+
+        assert_eq!(
+            "https://api.datamuse.com/words?ipa=1&v=enwiki&ml=ocean&rel_rhy=sea&max=20&topics=water%2Cboat&md=pr",
+            request.build().unwrap().request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn query_from_default_config() {
+        let client = DatamuseClient::new();
+        let request = client.query_from_config(QueryConfig {
+            hint_string: Some(String::from("hel")),
+            endpoint: Some(EndPoint::Suggest),
+            ..QueryConfig::default()
+        });
+
+        assert_eq!(
+            "https://api.datamuse.com/sug?s=hel",
+            request.build().unwrap().request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn accept_language_header() {
+        let client = DatamuseClient::new().with_accept_language("es-ES");
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Words)
+            .means_like("feliz")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "es-ES",
+            request.request.headers()[reqwest::header::ACCEPT_LANGUAGE]
+        );
+    }
+
+    #[test]
+    fn invalid_header_fail() {
+        let client = DatamuseClient::new().with_accept_language("es\n");
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Words)
+            .means_like("feliz");
+
+        assert!(request.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn unusual_words_only() {
+        let server = MockServer::routes(vec![(
+            "ml=happy&md=f",
+            MockResponse::json(
+                r#"[
+                    {"word":"glad","score":300,"tags":["f:25.3"]},
+                    {"word":"jubilant","score":200,"tags":["f:0.9"]},
+                    {"word":"blithe","score":100,"tags":["f:0.4"]},
+                    {"word":"merry","score":50}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("happy")
+            .unusual_words_only(1.0)
+            .list()
+            .await
+            .unwrap();
+
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["jubilant", "blithe"], words);
+    }
+
+    #[test]
+    fn into_builder_round_trip() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::EnglishWiki, EndPoint::Words)
+            .means_like("ocean")
+            .related(RelatedType::Rhyme, "sea")
+            .left_context("deep")
+            .add_topic("water")
+            .add_topic("boat")
+            .max_results(20)
+            .meta_data(MetaDataFlag::PartsOfSpeech)
+            .meta_data(MetaDataFlag::Pronunciation(PronunciationFormat::Ipa))
+            .build()
+            .unwrap();
+        let url = request.request.url().to_string();
+
+        let rebuilt = request.into_builder().unwrap().build().unwrap();
+
+        assert_eq!(url, rebuilt.request.url().as_str());
+    }
+
+    #[test]
+    fn into_builder_raw_param() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ocean")
+            .raw_param("foo", "bar")
+            .build()
+            .unwrap();
+        let url = request.url().to_string();
+
+        let rebuilt = request.into_builder().unwrap().build().unwrap();
+
+        assert_eq!("https://api.datamuse.com/words?ml=ocean&foo=bar", url);
+        assert_eq!(url, rebuilt.url().as_str());
+    }
+
+    #[test]
+    fn into_builder_options() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ocean")
+            .timeout(Duration::from_secs(3))
+            .retry(4)
+            .allow_unverified_params()
+            .strict_vocabulary()
+            .build()
+            .unwrap();
+
+        let builder = request.into_builder().unwrap();
+
+        assert_eq!(Some(Duration::from_secs(3)), builder.timeout);
+        assert_eq!(4, builder.max_attempts);
+        assert!(builder.allow_unverified);
+        assert!(builder.strict_vocabulary);
+    }
+
+    #[test]
+    fn into_builder_suggest() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Suggest)
+            .hint_string("hol")
+            .build()
+            .unwrap();
+
+        let rebuilt = request
+            .into_builder()
+            .unwrap()
+            .max_results(5)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/sug?v=es&s=hol&max=5",
+            rebuilt.request.url().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn max_response_bytes() {
+        let body = r#"[{"word":"milk","score":300},{"word":"cheese","score":200}]"#;
+        let server = MockServer::start(vec![MockResponse::json(body)]).await;
+
+        let client = server.client().with_max_response_bytes(body.len());
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .list()
+            .await
+            .unwrap();
+        assert_eq!(2, word_list.len());
+
+        let client = server.client().with_max_response_bytes(body.len() - 1);
+        let result = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .list()
+            .await;
+        match result {
+            Err(Error::ResponseTooLarge(limit)) => assert_eq!(body.len() - 1, limit),
+            val => panic!("Expected ResponseTooLarge, got {:?}", val),
+        }
+    }
+
+    #[tokio::test]
+    async fn paged() {
+        let server = MockServer::routes(vec![
+            (
+                "max=2",
+                MockResponse::json(r#"[{"word":"milk","score":300},{"word":"cheese","score":200}]"#),
+            ),
+            (
+                "max=4",
+                MockResponse::json(
+                    r#"[{"word":"milk","score":300},{"word":"cheese","score":200},{"word":"butter","score":100}]"#,
+                ),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let pages: Vec<Vec<String>> = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .paged(2)
+            .map(|page| page.unwrap().into_iter().map(|elem| elem.word).collect())
+            .collect()
+            .await;
+
+        assert_eq!(2, server.hits());
+        assert_eq!(
+            vec![
+                vec![String::from("milk"), String::from("cheese")],
+                vec![String::from("butter")],
+            ],
+            pages
+        );
+    }
+
+    #[tokio::test]
+    async fn paged_filtered_page() {
+        let server = MockServer::routes(vec![
+            (
+                "max=2",
+                MockResponse::json(
+                    r#"[{"word":"milk","score":300,"tags":["f:50.0"]},{"word":"cheese","score":200,"tags":["f:40.0"]}]"#,
+                ),
+            ),
+            (
+                "max=4",
+                MockResponse::json(
+                    r#"[{"word":"milk","score":300,"tags":["f:50.0"]},{"word":"cheese","score":200,"tags":["f:40.0"]},{"word":"ghee","score":100,"tags":["f:0.5"]}]"#,
+                ),
+            ),
+        ])
+        .await;
+        let client = server.client();
+
+        let pages: Vec<Vec<String>> = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .unusual_words_only(1.0)
+            .paged(2)
+            .map(|page| page.unwrap().into_iter().map(|elem| elem.word).collect())
+            .collect()
+            .await;
+
+        assert_eq!(2, server.hits());
+        assert_eq!(vec![vec![String::from("ghee")]], pages);
+    }
+
+    #[tokio::test]
+    async fn list_sourced() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"milk","score":300},{"word":"cheese","score":200}]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .list_sourced("dairy query")
+            .await
+            .unwrap();
+
+        assert_eq!(2, word_list.len());
+        assert_eq!("milk", word_list[0].element.word);
+        assert!(word_list.iter().all(|elem| elem.source == "dairy query"));
+    }
+
+    #[tokio::test]
+    async fn send_raw() {
+        let server = MockServer::start(vec![MockResponse::json(r#"[{"word":"milk"}]"#)]).await;
+        let client = server.client();
+
+        let response = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("dairy")
+            .build()
+            .unwrap()
+            .send_raw()
+            .await
+            .unwrap();
+
+        assert_eq!(reqwest::StatusCode::OK, response.status());
+        assert_eq!(r#"[{"word":"milk"}]"#, response.text().await.unwrap());
+    }
+
+    #[test]
+    fn likely_empty() {
+        let client = DatamuseClient::new();
+        let new_query = || client.new_query(Vocabulary::English, EndPoint::Words);
+
+        assert!(new_query().likely_empty());
+        assert!(new_query()
+            .meta_data(MetaDataFlag::Definitions)
+            .likely_empty());
+        assert!(new_query().means_like("cow").max_results(0).likely_empty());
+        assert!(new_query()
+            .spelled_like("elephant")
+            .sounds_like("jar")
+            .likely_empty());
+
+        assert!(!new_query().means_like("cow").likely_empty());
+        assert!(!new_query()
+            .spelled_like("b*")
+            .means_like("cow")
+            .likely_empty());
+        assert!(!new_query().spelled_like("elephant").likely_empty());
+        assert!(!client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("hel")
+            .likely_empty());
+    }
+
+    #[test]
+    fn lowercase_inputs() {
+        let client = DatamuseClient::new().lowercase_inputs(true);
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("Ocean")
+            .related(RelatedType::Rhyme, "SEA")
+            .spelled_like("B*");
+
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=ocean&rel_rhy=sea&sp=B*",
+            request.build().unwrap().request.url().as_str()
+        );
+
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("Ocean");
+
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=Ocean",
+            request.build().unwrap().request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn normalization() {
+        let build_with = |rules: NormalizationRules| {
+            let client = DatamuseClient::new().with_normalization(rules);
+            let request = client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like("  Crème   Brûlée ")
+                .spelled_like("Ca*")
+                .build()
+                .unwrap();
+            String::from(request.url().query().unwrap())
+        };
+
+        assert_eq!(
+            "ml=++Cr%C3%A8me+++Br%C3%BBl%C3%A9e+&sp=Ca*",
+            build_with(NormalizationRules::default())
+        );
+        assert_eq!(
+            "ml=Cr%C3%A8me+++Br%C3%BBl%C3%A9e&sp=Ca*",
+            build_with(NormalizationRules {
+                trim: true,
+                ..NormalizationRules::default()
+            })
+        );
+        assert_eq!(
+            "ml=++cr%C3%A8me+++br%C3%BBl%C3%A9e+&sp=Ca*",
+            build_with(NormalizationRules {
+                lowercase: true,
+                ..NormalizationRules::default()
+            })
+        );
+        assert_eq!(
+            "ml=++Creme+++Brulee+&sp=Ca*",
+            build_with(NormalizationRules {
+                strip_accents: true,
+                ..NormalizationRules::default()
+            })
+        );
+        assert_eq!(
+            "ml=Cr%C3%A8me+Br%C3%BBl%C3%A9e&sp=Ca*",
+            build_with(NormalizationRules {
+                collapse_whitespace: true,
+                ..NormalizationRules::default()
+            })
+        );
+
+        let client = DatamuseClient::new().with_normalization(NormalizationRules {
+            trim: true,
+            lowercase: true,
+            strip_accents: true,
+            collapse_whitespace: true,
+        });
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like(" Café ")
+            .add_topic("Épée")
+            .build()
+            .unwrap();
+        assert_eq!("sp=cafe&topics=epee", request.url().query().unwrap());
+    }
+
+    #[tokio::test]
+    async fn frequency_band() {
+        let server = MockServer::routes(vec![(
+            "ml=happy&md=f",
+            MockResponse::json(
+                r#"[
+                    {"word":"glad","score":300,"tags":["f:25.3"]},
+                    {"word":"cheerful","score":250,"tags":["f:5.0"]},
+                    {"word":"joyful","score":200,"tags":["f:2.1"]},
+                    {"word":"blithe","score":100,"tags":["f:0.4"]}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("happy")
+            .frequency_band(1.0, 5.0)
+            .list()
+            .await
+            .unwrap();
+
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["cheerful", "joyful"], words);
+    }
+
+    #[test]
+    fn frequency_band_inverted() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("happy")
+            .frequency_band(5.0, 1.0);
+
+        match request.build() {
+            Err(Error::ParameterError(_)) => (),
+            val => panic!("Expected ParameterError, got {:?}", val),
+        }
+    }
+
+    #[tokio::test]
+    async fn suggestions_sorted() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"hello world"},{"word":"hello"},{"word":"hello kitty"},{"word":"help"}]"#,
+        )])
+        .await;
+        let client = server.client();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("hel");
+
+        let mut orders: Vec<Vec<String>> = Vec::new();
+        for sort in &[
+            SuggestSort::ApiOrder,
+            SuggestSort::Alphabetical,
+            SuggestSort::ByLength,
+        ] {
+            let word_list = request.suggestions_sorted(*sort).await.unwrap();
+            orders.push(word_list.into_iter().map(|elem| elem.word).collect());
+        }
+
+        assert_eq!(
+            vec!["hello world", "hello", "hello kitty", "help"],
+            orders[0]
+        );
+        assert_eq!(
+            vec!["hello", "hello kitty", "hello world", "help"],
+            orders[1]
+        );
+        assert_eq!(
+            vec!["help", "hello", "hello world", "hello kitty"],
+            orders[2]
+        );
+    }
+
+    #[tokio::test]
+    async fn exclude_seed() {
+        let server = MockServer::routes(vec![(
+            "rel_rhy=cat",
+            MockResponse::json(
+                r#"[
+                    {"word":"hat","score":300},
+                    {"word":"Cat","score":200},
+                    {"word":"bat","score":100}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Rhyme, "cat")
+            .exclude_seed("cat")
+            .list()
+            .await
+            .unwrap();
+
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["hat", "bat"], words);
+    }
+
+    #[tokio::test]
+    async fn request_url() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ocean")
+            .max_results(3)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=ocean&max=3",
+            request.url().as_str()
+        );
+
+        //The mock server only answers requests for exactly this path and query
+        let request_line = format!(
+            "GET {}?{} ",
+            request.url().path(),
+            request.url().query().unwrap()
+        );
+        let server = MockServer::routes(vec![(
+            request_line.as_str(),
+            MockResponse::json(r#"[{"word":"sea","score":100}]"#),
+        )])
+        .await;
+        let client = server.client();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ocean")
+            .max_results(3)
+            .build()
+            .unwrap();
+
+        assert_eq!("sea", request.send().await.unwrap().list().unwrap()[0].word);
+    }
+
+    #[tokio::test]
+    async fn timeout() {
+        let server = MockServer::start(vec![
+            MockResponse::json("[]").delayed(Duration::from_millis(200))
+        ])
+        .await;
+        let client = server.client();
+
+        let result = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("slow")
+            .timeout(Duration::from_nanos(1))
+            .send()
+            .await;
+
+        match result {
+            Err(Error::ReqwestError(err)) => assert!(err.is_timeout()),
+            val => panic!("Expected ReqwestError, got {:?}", val),
+        }
+    }
+
+    #[tokio::test]
+    async fn retry() {
+        let server = MockServer::start(vec![
+            MockResponse::status(429, ""),
+            MockResponse::status(429, ""),
+            MockResponse::json(r#"[{"word":"milk","score":100}]"#),
+        ])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .retry(3)
+            .list()
+            .await
+            .unwrap();
+
+        assert_eq!("milk", word_list[0].word);
+        assert_eq!(3, server.hits());
+    }
+
+    #[tokio::test]
+    async fn retries_exhausted() {
+        let server = MockServer::start(vec![MockResponse::status(503, "unavailable")]).await;
+        let client = server.client();
+
+        let result = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .retry(2)
+            .send()
+            .await;
+
+        match result {
+            Err(Error::RetriesExhausted(attempts, status)) => {
+                assert_eq!(2, attempts);
+                assert_eq!(503, status.as_u16());
+            }
+            val => panic!("Expected RetriesExhausted, got {:?}", val),
+        }
+        assert_eq!(2, server.hits());
+    }
+
+    #[test]
+    fn spelled_exactly() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_exactly("colour")
+            .meta_data(MetaDataFlag::Definitions)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=colour&md=d",
+            request.url().as_str()
+        );
+
+        for word in &["col*r", "colo?r", "c#lour"] {
+            let request = client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .spelled_exactly(word);
+            match request.build() {
+                Err(Error::ParameterError(_)) => (),
+                val => panic!("Expected ParameterError, got {:?}", val),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn http_status() {
+        let server = MockServer::start(vec![MockResponse::status(
+            503,
+            "<html>Service Unavailable</html>",
+        )])
+        .await;
+        let client = server.client();
+
+        let result = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .send()
+            .await;
+
+        match result {
+            Err(Error::HttpStatus(status, body)) => {
+                assert_eq!(503, status.as_u16());
+                assert_eq!("<html>Service Unavailable</html>", body);
+            }
+            val => panic!("Expected HttpStatus, got {:?}", val),
+        }
+    }
+
+    #[tokio::test]
+    async fn suggest_without_scores() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"hello","score":900},{"word":"hello world"}]"#,
+        )])
+        .await;
+        let client = server.client();
+
+        let word_list = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("hel")
+            .list()
+            .await
+            .unwrap();
+
+        assert_eq!(2, word_list.len());
+        assert_eq!(Some(900), word_list[0].score);
+        assert_eq!(None, word_list[1].score);
+    }
+
+    #[test]
+    fn max_results_range() {
+        let client = DatamuseClient::new();
+        for max in &[0, 1001] {
+            let request = client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .means_like("cow")
+                .max_results(*max);
+            match request.build() {
+                Err(Error::ParameterError(_)) => (),
+                val => panic!("Expected ParameterError, got {:?}", val),
+            }
+        }
+
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .max_results(1000)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=cow&max=1000",
+            request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn clone_builder() {
+        let client = DatamuseClient::new();
+        let base = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .add_topic("sea");
+
+        let rhymes = base.clone().related(RelatedType::Rhyme, "boat");
+        let synonyms = base.clone().related(RelatedType::Synonym, "boat");
+
+        assert_eq!(
+            "https://api.datamuse.com/words?rel_rhy=boat&topics=sea",
+            rhymes.build().unwrap().url().as_str()
+        );
+        assert_eq!(
+            "https://api.datamuse.com/words?rel_syn=boat&topics=sea",
+            synonyms.build().unwrap().url().as_str()
+        );
+        assert_eq!(
+            "https://api.datamuse.com/words?topics=sea",
+            base.build().unwrap().url().as_str()
+        );
+    }
+
+    #[test]
+    fn raw_param() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Suggest)
+            .hint_string("gat")
+            .raw_param("qe", "sp")
+            .raw_param("unknown", "a b")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/sug?v=es&s=gat&qe=sp&unknown=a+b",
+            request.url().as_str()
+        );
+    }
+
+    #[tokio::test]
+    async fn snapshot_url() {
+        let server = MockServer::start(vec![MockResponse::json(r#"[{"word":"milk"}]"#)]).await;
+        let client = server.client();
+
+        let snapshot = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow")
+            .send()
+            .await
+            .unwrap()
+            .snapshot()
+            .unwrap();
+
+        assert!(snapshot.url.unwrap().ends_with("/words?ml=cow"));
+        assert_eq!("milk", snapshot.words[0].word);
+    }
+
+    #[tokio::test]
+    async fn median_frequency() {
+        let server = MockServer::routes(vec![(
+            "ml=happy&md=f",
+            MockResponse::json(
+                r#"[
+                    {"word":"glad","score":300,"tags":["f:25.3"]},
+                    {"word":"jubilant","score":200,"tags":["f:0.9"]},
+                    {"word":"blithe","score":100,"tags":["f:0.4"]},
+                    {"word":"merry","score":50}
+                ]"#,
+            ),
+        )])
+        .await;
+        let client = server.client();
+
+        let median = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("happy")
+            .median_frequency()
+            .await
+            .unwrap();
+
+        assert_eq!(Some(0.9), median);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(Some(3.0), super::median(vec![5.0, 1.0, 3.0]));
+        assert_eq!(Some(2.5), super::median(vec![4.0, 1.0, 3.0, 2.0]));
+        assert_eq!(Some(7.0), super::median(vec![7.0]));
+        assert_eq!(None, super::median(Vec::new()));
+    }
+
+    #[tokio::test]
+    async fn best() {
+        let server = MockServer::routes(vec![(
+            "s=hel",
+            MockResponse::json(r#"[{"word":"hello","score":900},{"word":"help","score":800}]"#),
+        )])
+        .await;
+        let client = server.client();
+
+        let best = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("hel")
+            .best()
+            .await
+            .unwrap();
+        assert_eq!("hello", best.unwrap().word);
+
+        let best = client
+            .new_query(Vocabulary::English, EndPoint::Suggest)
+            .hint_string("xq")
+            .best()
+            .await
+            .unwrap();
+        assert_eq!(None, best);
+    }
+
+    #[test]
+    fn as_curl() {
+        let client = DatamuseClient::new().with_accept_language("en-US");
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ring")
+            .max_results(5)
+            .build()
+            .unwrap();
+        let command = request.as_curl();
+
+        assert!(command.starts_with("curl"));
+        assert!(command.contains(&format!("'{}'", request.url())));
+        assert!(command.contains("-H 'accept-language: en-US'"));
+    }
+
+    #[test]
+    fn shell_quote_single_quote() {
+        assert_eq!(r#"'it'\''s'"#, super::shell_quote("it's"));
+    }
+
+    #[tokio::test]
+    async fn gzip_response() {
+        let server =
+            MockServer::start(vec![MockResponse::gzip(r#"[{"word":"ring","score":100}]"#)]).await;
+        let client = server.client();
+        let words = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("bell")
+            .list()
+            .await
+            .unwrap();
+
+        assert_eq!("ring", words[0].word);
+    }
+
+    #[test]
+    fn topics_join() {
+        let build = |topics: &[&str]| {
+            let topics = topics.iter().map(|topic| topic.to_string()).collect();
+            Parameter::Topics(topics)
+                .build(&Vocabulary::English, &EndPoint::Words, false)
+                .unwrap()
+        };
+
+        assert_eq!((String::from("topics"), String::new()), build(&[]));
+        assert_eq!(
+            (String::from("topics"), String::from("sea")),
+            build(&["sea"])
+        );
+        assert_eq!(
+            (String::from("topics"), String::from("sea,boat,fish")),
+            build(&["sea", "boat", "fish"])
+        );
+    }
+
+    #[test]
+    fn allow_unverified_params() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Words)
+            .related(RelatedType::Synonym, "feliz");
+        assert!(matches!(
+            request.clone().build(),
+            Err(Error::VocabularyError(_))
+        ));
+
+        let request = request.allow_unverified_params().build().unwrap();
+        assert_eq!(
+            "https://api.datamuse.com/words?v=es&rel_syn=feliz",
+            request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn query_echo() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like("flower")
+            .query_echo(QueryEcho::SpelledLike)
+            .meta_data(MetaDataFlag::WordFrequency)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=flower&md=f&qe=sp",
+            request.url().as_str()
+        );
+        assert!(request.into_builder().unwrap().build().is_ok());
+    }
+
+    #[test]
+    fn query_echo_missing_parameter() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like("flower")
+            .query_echo(QueryEcho::MeansLike);
+
+        assert!(matches!(request.build(), Err(Error::ParameterError(_))));
+    }
+
+    #[test]
+    fn related_all() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related_all(&[
+                (RelatedType::Rhyme, "moon"),
+                (RelatedType::Trigger, "night"),
+                (RelatedType::Synonym, "bright"),
+            ])
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/words?rel_rhy=moon&rel_trg=night&rel_syn=bright",
+            request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn spelled_like_length() {
+        let client = DatamuseClient::new();
+        let url = |pattern: &str, exact_len: usize| {
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .spelled_like_length(pattern, exact_len)
+                .build()
+                .map(|request| request.url().to_string())
+        };
+
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=%3F%3F%3F%3F%3F",
+            url("*", 5).unwrap()
+        );
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=b%3F%3Fk",
+            url("b*k", 4).unwrap()
+        );
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=c%3Ft",
+            url("c?t", 3).unwrap()
+        );
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=cat",
+            url("cat*", 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn spelled_like_length_mismatch() {
+        let client = DatamuseClient::new();
+        let build = |pattern: &str, exact_len: usize| {
+            client
+                .new_query(Vocabulary::English, EndPoint::Words)
+                .spelled_like_length(pattern, exact_len)
+                .build()
+        };
+
+        assert!(matches!(build("c?t", 4), Err(Error::ParameterError(_))));
+        assert!(matches!(build("ca*ts", 3), Err(Error::ParameterError(_))));
+        assert!(matches!(build("*a*", 5), Err(Error::ParameterError(_))));
+        assert!(matches!(build("*", 0), Err(Error::ParameterError(_))));
+        assert!(matches!(build("", 0), Err(Error::ParameterError(_))));
+    }
+
+    #[test]
+    fn strict_vocabulary() {
+        let client = DatamuseClient::new();
+        let query = |vocabulary: Vocabulary| {
+            client
+                .new_query(vocabulary, EndPoint::Words)
+                .related(RelatedType::Rhyme, "ocean")
+        };
+
+        assert!(query(Vocabulary::EnglishWiki)
+            .strict_vocabulary()
+            .build()
+            .is_ok());
+        assert!(matches!(
+            query(Vocabulary::Spanish).strict_vocabulary().build(),
+            Err(Error::VocabularyError(_))
+        ));
+        assert!(query(Vocabulary::EnglishWiki).build().is_ok());
+    }
+
+    #[tokio::test]
+    async fn words_only() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[{"word":"milk","score":300},{"word":"bull","score":200}]"#,
+        )])
+        .await;
+        let client = server.client();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("cow");
+
+        let words = request.words_only().await.unwrap();
+        let expected: Vec<String> = request
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|elem| elem.word)
+            .collect();
+        assert_eq!(expected, words);
+        assert_eq!(vec!["milk", "bull"], words);
+    }
+
+    #[test]
+    fn context() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Follower, "drink")
+            .context(Some("hot"), Some("cup"));
+
+        assert_eq!(
+            "https://api.datamuse.com/words?rel_bga=drink&lc=hot&rc=cup",
+            request.build().unwrap().url().as_str()
+        );
+
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .spelled_like("t*")
+            .context(None, Some("cup"));
+
+        assert_eq!(
+            "https://api.datamuse.com/words?sp=t*&rc=cup",
+            request.build().unwrap().url().as_str()
+        );
+    }
+
+    #[test]
+    fn context_without_anchor() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .related(RelatedType::Rhyme, "cup")
+            .context(Some("hot"), None);
+
+        assert!(matches!(request.build(), Err(Error::ParameterError(_))));
+    }
+
+    #[test]
+    fn normalization_of_each_topic() {
+        let client = DatamuseClient::new().with_normalization(NormalizationRules {
+            trim: true,
+            lowercase: true,
+            strip_accents: false,
+            collapse_whitespace: true,
+        });
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("ship")
+            .add_topic("Sea ")
+            .add_topic(" boat")
+            .add_topic("open   water")
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/words?ml=ship&topics=sea%2Cboat%2Copen+water",
+            request.url().as_str()
+        );
+    }
+
+    #[test]
+    fn strict_vocabulary_with_unverified_params() {
+        let client = DatamuseClient::new();
+        let request = client
+            .new_query(Vocabulary::Spanish, EndPoint::Words)
+            .related(RelatedType::Synonym, "feliz")
+            .strict_vocabulary()
+            .allow_unverified_params()
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            "https://api.datamuse.com/words?v=es&rel_syn=feliz",
+            request.url().as_str()
+        );
+    }
 }
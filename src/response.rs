@@ -1,54 +1,153 @@
-use crate::Result;
-use serde::Deserialize;
+use crate::{Error, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{self, Display, Formatter};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// This struct represents each word and its associated data in the response.
 /// It is constructed when parsing a [Response](Response) with the method list().
 /// Note that all optional values can still be None even if the proper flag
-/// is set
-#[derive(Debug, PartialEq)]
+/// is set. It can be serialized and deserialized with serde, e.g. to store results
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct WordElement {
     /// The word returned based on the search parameters
     pub word: String,
     /// A score which ranks the word based on how well it fit the provided parameters.
-    /// Note that by default the words are ranked by score from highest to lowest
-    pub score: usize,
+    /// Note that by default the words are ranked by score from highest to lowest.
+    /// This is None if the api did not return a score for the word, which can happen for
+    /// the "suggest" endpoint. Previously a missing score was not supported, code which
+    /// used the score directly can use `score.unwrap_or(0)` to keep the same ordering
+    pub score: Option<usize>,
     /// The number of syllables the word has. This will only have a value if
     /// the meta data flag [SyllableCount](crate::MetaDataFlag::SyllableCount) is set
     pub num_syllables: Option<usize>,
     /// The part(s) of speech a word can be. This will only have a value if
     /// the meta data flag [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech) is set
     pub parts_of_speech: Option<Vec<PartOfSpeech>>,
-    /// The pronunciation of the word. This will only have a value if
-    /// the meta data flag [Pronunciation](crate::MetaDataFlag::Pronunciation) is set.
-    /// If an IPA pronuncation is available, it takes precedence as it is optional
-    pub pronunciation: Option<String>,
+    /// The ARPABET pronunciation of the word. This will only have a value if the meta data flag
+    /// [Pronunciation](crate::MetaDataFlag::Pronunciation) is set. The api also returns it
+    /// if the IPA format is requested, in which case both pronunciations are available
+    pub pronunciation_arpabet: Option<String>,
+    /// The IPA pronunciation of the word. This will only have a value if the meta data flag
+    /// [Pronunciation](crate::MetaDataFlag::Pronunciation) is set with the
+    /// [Ipa](crate::PronunciationFormat::Ipa) format
+    pub pronunciation_ipa: Option<String>,
     /// The frequency of a word based on how many times the word is used per 1,000,000
     /// words of text. This will only have a value if the meta data flag
     /// [WordFrequency](crate::MetaDataFlag::WordFrequency) is set
-    pub frequency: Option<f32>,
+    pub frequency: Option<f64>,
+    /// The frequency exactly as given by the api (e.g. "16.567268"). This is kept even if it
+    /// could not be parsed into [frequency](WordElement::frequency)
+    #[serde(default)]
+    pub frequency_raw: Option<String>,
     /// Definitions of a word and the associated part of speech with its use. This will only
     /// have a value if the meta data flag [Definitions](crate::MetaDataFlag::Definitions) is set
     pub definitions: Option<Vec<Definition>>,
+    /// The word the definitions belong to if it differs from the word itself, e.g. "run" for the
+    /// inflected form "ran". This is given by the api as "defHeadword" together with definitions
+    #[serde(default)]
+    pub definition_headword: Option<String>,
+    /// Whether the word is typically capitalized, such as proper nouns. This is true if the api
+    /// tags the word as a proper noun ("prop", only given with the meta data flag
+    /// [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech)) or if the returned word itself
+    /// contains uppercase letters
+    pub typically_capitalized: bool,
+    /// All tags returned by the api for the word exactly as given, including those which are
+    /// not parsed into the other fields (e.g. "prop" or tags added to the api in the future)
+    #[serde(default)]
+    pub raw_tags: Vec<String>,
 }
 
 /// A struct representing a word definition
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Definition {
     /// The part of speech associated with the definition
     pub part_of_speech: Option<PartOfSpeech>,
+    /// The part of speech code exactly as given by the api (e.g. "n" or "u" for unknown).
+    /// This is kept even if the code does not match any [PartOfSpeech](PartOfSpeech)
+    pub part_of_speech_code: String,
     /// The definition itself
     pub definition: String,
 }
 
+/// A struct pairing a word with a label of the query which returned it.
+/// This is returned by [list_sourced()](crate::RequestBuilder::list_sourced) and allows
+/// keeping track of where each word came from when combining the results of multiple queries
+#[derive(Clone, Debug, PartialEq)]
+pub struct SourcedWord {
+    /// The word and its associated data
+    pub element: WordElement,
+    /// The label of the query which returned the word
+    pub source: String,
+}
+
+/// A single autocompletion returned by the "suggest" endpoint, see
+/// [suggestions()](Response::suggestions). Unlike [WordElement](WordElement) it only contains
+/// the suggested word and its position among the suggestions
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The suggested word or phrase
+    pub word: String,
+    /// The position of the suggestion in the order given by the api, starting at 1 for the
+    /// most likely completion
+    pub rank: usize,
+}
+
+/// A single phoneme of an ARPABET pronunciation as returned by
+/// [arpabet_phonemes()](WordElement::arpabet_phonemes)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Phoneme {
+    /// The ARPABET symbol of the phoneme without its stress marker (e.g. "AW")
+    pub symbol: String,
+    /// The stress of a vowel, where 0 is no stress, 1 is primary stress and 2 is secondary stress.
+    /// This is None for consonants
+    pub stress: Option<u8>,
+}
+
+/// A struct pairing a word with the words it rhymes with, as returned by
+/// [rhymes_with_any()](crate::DatamuseClient::rhymes_with_any)
+#[derive(Clone, Debug, PartialEq)]
+pub struct RhymeMatch {
+    /// The rhyming word and its associated data
+    pub element: WordElement,
+    /// The seed words the word rhymes with in the order they were given
+    pub seeds: Vec<String>,
+}
+
 /// A struct representing a response from a request.
 /// This can be parsed into a word list using the list() method
 #[derive(Debug)]
 pub struct Response {
     json: String,
+    keep_empty_words: bool,
+    url: Option<String>, //Only known if the response was received from the api
+}
+
+/// The version of the format of [Snapshot](Snapshot). It is increased whenever the fields of
+/// a snapshot or their meaning change
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// A struct archiving a response together with information about its query, as returned by
+/// [snapshot()](Response::snapshot). It can be serialized with serde (e.g. as json) to store
+/// the exact results of a query and later be read again with [replay()](Snapshot::replay)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// The version of the snapshot format, see [SNAPSHOT_SCHEMA_VERSION](SNAPSHOT_SCHEMA_VERSION)
+    pub schema_version: u32,
+    /// The url of the query or None if the response was not received from the api
+    pub url: Option<String>,
+    /// When the snapshot was taken in seconds since the unix epoch
+    pub timestamp: u64,
+    /// The body of the response exactly as received
+    pub json: String,
+    /// The words parsed from the response at the time of the snapshot
+    pub words: Vec<WordElement>,
 }
 
-/// An enum representing all possible parts of speech returned from the api
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// An enum representing all possible parts of speech returned from the api.
+/// With serde it is serialized as its lowercase name (e.g. "noun")
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PartOfSpeech {
     /// Noun
     Noun, //n
@@ -58,26 +157,344 @@ pub enum PartOfSpeech {
     Adverb, //adv
     /// Verb
     Verb, //v
+    /// The api does not know the part of speech. This is different from parts_of_speech being
+    /// None, which means that no part of speech was returned at all
+    Unknown, //u
 }
 
 #[derive(Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 struct DatamuseWordObject {
     word: String,
-    score: usize,
+    score: Option<usize>,
+    #[serde(default, deserialize_with = "deserialize_num_syllables")]
     num_syllables: Option<usize>,
     tags: Option<Vec<String>>,
     defs: Option<Vec<String>>,
+    def_headword: Option<String>,
+}
+
+//Only the word of an entry, used to count the entries of a response without converting them
+#[derive(Deserialize, Debug)]
+struct DatamuseWordOnly {
+    word: String,
+}
+
+//The body returned by the api instead of a word list for some malformed requests
+#[derive(Deserialize, Debug)]
+struct DatamuseErrorObject {
+    message: String,
+}
+
+impl WordElement {
+    /// Returns whether the word's frequency is at least the given threshold (in uses per
+    /// 1,000,000 words). This returns None if the word has no frequency, which is the case
+    /// unless the meta data flag [WordFrequency](crate::MetaDataFlag::WordFrequency) is set
+    pub fn is_common(&self, threshold: f64) -> Option<bool> {
+        self.frequency.map(|frequency| frequency >= threshold)
+    }
+
+    /// Returns the IPA pronunciation of the word if available and otherwise the ARPABET
+    /// pronunciation. This was previously the pronunciation field
+    #[deprecated(note = "use the pronunciation_ipa or pronunciation_arpabet fields instead")]
+    pub fn pronunciation(&self) -> Option<&str> {
+        self.pronunciation_ipa
+            .as_deref()
+            .or(self.pronunciation_arpabet.as_deref())
+    }
+
+    /// Returns whether the word can be the given part of speech. This is false if parts_of_speech
+    /// is None, which is the case unless the meta data flag
+    /// [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech) is set
+    pub fn has_part_of_speech(&self, pos: PartOfSpeech) -> bool {
+        matches!(&self.parts_of_speech, Some(parts_of_speech) if parts_of_speech.contains(&pos))
+    }
+
+    /// Returns whether the word can be a noun, see [has_part_of_speech()](WordElement::has_part_of_speech)
+    pub fn is_noun(&self) -> bool {
+        self.has_part_of_speech(PartOfSpeech::Noun)
+    }
+
+    /// Returns whether the word can be a verb, see [has_part_of_speech()](WordElement::has_part_of_speech)
+    pub fn is_verb(&self) -> bool {
+        self.has_part_of_speech(PartOfSpeech::Verb)
+    }
+
+    /// Returns whether the word can be an adjective, see [has_part_of_speech()](WordElement::has_part_of_speech)
+    pub fn is_adjective(&self) -> bool {
+        self.has_part_of_speech(PartOfSpeech::Adjective)
+    }
+
+    /// Returns whether the word can be an adverb, see [has_part_of_speech()](WordElement::has_part_of_speech)
+    pub fn is_adverb(&self) -> bool {
+        self.has_part_of_speech(PartOfSpeech::Adverb)
+    }
+
+    /// Returns the most likely part of speech of the word, which is the first one in
+    /// parts_of_speech as the api orders them by likelihood. This returns None unless the meta
+    /// data flag [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech) is set
+    pub fn primary_pos(&self) -> Option<PartOfSpeech> {
+        self.parts_of_speech.as_ref()?.first().copied()
+    }
+
+    /// Returns the ARPABET pronunciation of the word as a list of phonemes with the stress
+    /// markers of the vowels separated from their symbols (e.g. "K AW1 " gives the phonemes
+    /// "K" without stress and "AW" with stress 1). This returns None if there is no pronunciation
+    /// or it is not in the ARPABET format
+    pub fn arpabet_phonemes(&self) -> Option<Vec<Phoneme>> {
+        let phonemes = self
+            .arpabet_tokens()?
+            .into_iter()
+            .map(|token| {
+                let symbol = token.trim_end_matches(|c: char| c.is_ascii_digit());
+                Phoneme {
+                    symbol: String::from(symbol),
+                    stress: token[symbol.len()..].parse().ok(),
+                }
+            })
+            .collect();
+
+        Some(phonemes)
+    }
+
+    /// Returns the ARPABET pronunciation of the word split into syllables, each of which is a list
+    /// of phonemes (e.g. "water" gives `[["W", "AO1"], ["T", "ER0"]]`). Every syllable contains
+    /// exactly one vowel phoneme. A single consonant between two vowels starts the next syllable,
+    /// while for multiple consonants the first one ends the previous syllable. This returns None
+    /// if there is no pronunciation or it is not in the ARPABET format
+    pub fn arpabet_syllables(&self) -> Option<Vec<Vec<String>>> {
+        let phonemes = self.arpabet_tokens()?;
+        let vowels: Vec<usize> = phonemes
+            .iter()
+            .enumerate()
+            .filter(|(_, phoneme)| is_arpabet_vowel(phoneme))
+            .map(|(i, _)| i)
+            .collect();
+
+        if vowels.is_empty() {
+            return None;
+        }
+
+        let mut syllables: Vec<Vec<String>> = Vec::new();
+        let mut start = 0;
+        for window in vowels.windows(2) {
+            let consonants = window[1] - window[0] - 1;
+            let end = if consonants > 1 {
+                window[0] + 2
+            } else {
+                window[0] + 1
+            };
+
+            syllables.push(
+                phonemes[start..end]
+                    .iter()
+                    .map(|val| val.to_string())
+                    .collect(),
+            );
+            start = end;
+        }
+        syllables.push(
+            phonemes[start..]
+                .iter()
+                .map(|val| val.to_string())
+                .collect(),
+        );
+
+        Some(syllables)
+    }
+
+    //Splits the pronunciation into its phonemes if it is given in ARPABET (e.g. "K AW1 ")
+    pub(crate) fn arpabet_tokens(&self) -> Option<Vec<&str>> {
+        let pronunciation = self.pronunciation_arpabet.as_ref()?;
+        let tokens: Vec<&str> = pronunciation.split_whitespace().collect();
+
+        let is_arpabet = tokens.iter().all(|token| {
+            let symbol = token.trim_end_matches(|c: char| c.is_ascii_digit());
+            !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_uppercase())
+        });
+
+        if tokens.is_empty() || !is_arpabet {
+            None
+        } else {
+            Some(tokens)
+        }
+    }
+}
+
+impl Display for WordElement {
+    //Formats the word as e.g. "cow (score: 2168, syllables: 1) - mature female of mammals"
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.word)?;
+
+        let mut details = Vec::new();
+        if let Some(score) = self.score {
+            details.push(format!("score: {}", score));
+        }
+        if let Some(num_syllables) = self.num_syllables {
+            details.push(format!("syllables: {}", num_syllables));
+        }
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+
+        if let Some(definition) = self.definitions.as_ref().and_then(|defs| defs.first()) {
+            write!(f, " - {}", definition.gloss())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Definition {
+    /// Returns the definition without any example sentences. Definitions are given in the form
+    /// `gloss; "example"; "example"`, so this returns all parts which are not quoted
+    pub fn gloss(&self) -> String {
+        definition_parts(&self.definition)
+            .into_iter()
+            .filter(|part| !is_quoted(part))
+            .collect::<Vec<&str>>()
+            .join("; ")
+    }
+
+    /// Returns the example sentences given in the definition without their quotes. This is empty if
+    /// the definition contains no examples
+    pub fn examples(&self) -> Vec<String> {
+        definition_parts(&self.definition)
+            .into_iter()
+            .filter(|part| is_quoted(part))
+            .map(|part| part[1..part.len() - 1].to_string())
+            .collect()
+    }
 }
 
 impl Response {
-    /// Parses the response into a list of word elements
+    /// Parses the response into a list of word elements. By default entries whose word
+    /// is empty or only contains whitespace are left out, see keep_empty_words()
     pub fn list(&self) -> Result<Vec<WordElement>> {
-        parse_response(&self.json)
+        parse_response(&self.json, self.keep_empty_words)
+    }
+
+    /// Parses the response like list() and returns an iterator over its words. This allows
+    /// the words to be used in a for loop without binding the list first
+    pub fn iter(&self) -> Result<impl Iterator<Item = WordElement>> {
+        Ok(self.list()?.into_iter())
+    }
+
+    /// Parses the response like list() and returns only the words themselves without their
+    /// scores or metadata
+    pub fn words(&self) -> Result<Vec<String>> {
+        Ok(self.list()?.into_iter().map(|elem| elem.word).collect())
+    }
+
+    /// Parses the response of a query to the "suggest" endpoint into a list of suggestions ranked
+    /// in the order given by the api. list() can still be used for these responses, but leaves
+    /// most fields of each [WordElement](WordElement) empty
+    pub fn suggestions(&self) -> Result<Vec<Suggestion>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, elem)| Suggestion {
+                word: elem.word,
+                rank: index + 1,
+            })
+            .collect())
+    }
+
+    /// Returns the number of words in the response, which is the length of the list returned by
+    /// list(). Only the words themselves are parsed, so this is cheaper than calling list() when
+    /// only the count is needed
+    pub fn len(&self) -> Result<usize> {
+        let json = self.json.strip_prefix('\u{feff}').unwrap_or(&self.json);
+        match serde_json::from_str::<Vec<DatamuseWordOnly>>(json) {
+            Ok(word_list) => Ok(word_list
+                .iter()
+                .filter(|elem| self.keep_empty_words || !elem.word.trim().is_empty())
+                .count()),
+            Err(_) => self.list().map(|word_list| word_list.len()), //Returns the same error as list()
+        }
+    }
+
+    /// Returns whether the response contains no words, see len()
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Parses the response and returns only its first word, which is the best match of the query,
+    /// or None if the response contains no words
+    pub fn best(&self) -> Result<Option<WordElement>> {
+        Ok(self.list()?.into_iter().next())
+    }
+
+    /// Parses the response into a list of word elements like list(), but only keeps the words
+    /// which can be the given part of speech. Words without parts of speech are left out, so the
+    /// meta data flag [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech) should be set
+    pub fn list_filtered(&self, pos: PartOfSpeech) -> Result<Vec<WordElement>> {
+        let mut word_list = self.list()?;
+        word_list.retain(|elem| elem.has_part_of_speech(pos));
+
+        Ok(word_list)
+    }
+
+    /// Parses the json of the response into any type which can be deserialized with serde.
+    /// This allows fields of the api which are not supported by [WordElement](WordElement)
+    /// to be read while still using this library to build and send requests
+    pub fn parse_into<T: DeserializeOwned>(&self) -> Result<T> {
+        Ok(serde_json::from_str(&self.json)?)
+    }
+
+    /// Sets whether entries with an empty or whitespace-only word should be kept when
+    /// parsing the response. By default these entries are removed
+    pub fn keep_empty_words(mut self, keep: bool) -> Self {
+        self.keep_empty_words = keep;
+
+        self
+    }
+
+    /// Returns a [Snapshot](Snapshot) of the response containing the parsed words, the url of
+    /// the query and the current time. An error is returned if the response cannot be parsed
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        Ok(Snapshot {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            url: self.url.clone(),
+            timestamp,
+            json: self.json.clone(),
+            words: self.list()?,
+        })
+    }
+
+    /// Returns a response for the given json as returned by the api, e.g. read from a file in
+    /// which earlier results were archived. It can be parsed like any other response without
+    /// sending a request
+    pub fn from_json(json: String) -> Response {
+        Response::new(json)
     }
 
     pub(crate) fn new(json: String) -> Response {
-        Response { json }
+        Response {
+            json,
+            keep_empty_words: false,
+            url: None,
+        }
+    }
+
+    pub(crate) fn with_url(mut self, url: String) -> Self {
+        self.url = Some(url);
+
+        self
+    }
+}
+
+impl Snapshot {
+    /// Parses the archived body of the response again without sending a new request. As with
+    /// [list()](Response::list) entries with an empty word are left out
+    pub fn replay(&self) -> Result<Vec<WordElement>> {
+        parse_response(&self.json, false)
     }
 }
 
@@ -88,16 +505,87 @@ impl PartOfSpeech {
             "adj" => Some(Self::Adjective),
             "adv" => Some(Self::Adverb),
             "v" => Some(Self::Verb),
-            _ => None, //Also catches undefined option "u"
+            "u" => Some(Self::Unknown),
+            _ => None,
         }
     }
 }
 
-fn parse_response(response: &str) -> Result<Vec<WordElement>> {
-    let word_list: Vec<DatamuseWordObject> = serde_json::from_str(response)?;
+//Splits a definition at each ';' which is not inside of quotes
+fn definition_parts(definition: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quotes = false;
+    let mut start = 0;
+
+    for (i, c) in definition.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                parts.push(definition[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    parts.push(definition[start..].trim());
+
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+fn is_quoted(part: &str) -> bool {
+    part.len() >= 2 && part.starts_with('"') && part.ends_with('"')
+}
+
+fn is_arpabet_vowel(phoneme: &str) -> bool {
+    matches!(
+        phoneme.trim_end_matches(|c: char| c.is_ascii_digit()),
+        "AA" | "AE"
+            | "AH"
+            | "AO"
+            | "AW"
+            | "AY"
+            | "EH"
+            | "ER"
+            | "EY"
+            | "IH"
+            | "IY"
+            | "OW"
+            | "OY"
+            | "UH"
+            | "UW"
+    )
+}
+
+//Some responses give the number of syllables as a float (e.g. 1.0), so it is rounded
+fn deserialize_num_syllables<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<usize>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let num_syllables: Option<f64> = Option::deserialize(deserializer)?;
+
+    Ok(num_syllables.map(|val| val.round() as usize))
+}
+
+fn parse_response(response: &str, keep_empty_words: bool) -> Result<Vec<WordElement>> {
+    let response = response.strip_prefix('\u{feff}').unwrap_or(response); //Byte order mark added by some proxies
+    let word_list: Vec<DatamuseWordObject> = match serde_json::from_str(response) {
+        Ok(word_list) => word_list,
+        Err(err) => {
+            return match serde_json::from_str::<DatamuseErrorObject>(response) {
+                Ok(error) => Err(Error::ApiError(error.message)),
+                Err(_) => Err(Error::SerdeError(err)),
+            }
+        }
+    };
     let mut converted_word_list: Vec<WordElement> = Vec::new();
 
     for word in word_list {
+        if !keep_empty_words && word.word.trim().is_empty() {
+            continue;
+        }
+
         converted_word_list.push(word_obj_to_word_elem(word));
     }
 
@@ -110,63 +598,66 @@ fn word_obj_to_word_elem(word_obj: DatamuseWordObject) -> WordElement {
     let num_syllables = word_obj.num_syllables;
 
     let mut parts_of_speech: Vec<PartOfSpeech> = Vec::new();
-    let mut pronunciation = None;
+    let mut pronunciation_arpabet = None;
+    let mut pronunciation_ipa = None;
     let mut frequency = None;
+    let mut frequency_raw = None;
+    let mut definition_headword = word_obj.def_headword;
+    let mut typically_capitalized = word.chars().any(char::is_uppercase);
+
+    let raw_tags = word_obj.tags.unwrap_or_default();
+    for tag in &raw_tags {
+        let parts: Vec<&str> = tag.split(':').collect();
 
-    if let Some(tags) = word_obj.tags {
-        for tag in tags {
-            let parts: Vec<&str> = tag.split(':').collect();
-
-            match parts[0] {
-                "f" => {
-                    if parts.len() == 2 {
-                        frequency = match parts[1].parse() {
-                            Ok(val) => Some(val),
-                            Err(_) => None,
-                        }
-                    }
+        match parts[0] {
+            "f" => {
+                if parts.len() == 2 {
+                    frequency = parts[1].parse().ok();
+                    frequency_raw = Some(parts[1].to_string());
                 }
-                "pron" => {
-                    if let None = pronunciation {
-                        //If pronunciation already has a value ignore b/c of ipa
-                        if parts.len() == 2 {
-                            pronunciation = Some(parts[1].to_string());
-                        }
-                    }
+            }
+            "pron" => {
+                if parts.len() == 2 {
+                    pronunciation_arpabet = Some(parts[1].to_string());
                 }
-                "ipa_pron" => {
-                    if parts.len() == 2 {
-                        pronunciation = Some(parts[1].to_string());
-                    }
+            }
+            "ipa_pron" => {
+                if parts.len() == 2 {
+                    pronunciation_ipa = Some(parts[1].to_string());
+                }
+            }
+            "defHeadword" => {
+                if parts.len() == 2 {
+                    definition_headword = Some(parts[1].to_string());
                 }
-                val => match PartOfSpeech::from_str(&val) {
-                    Some(val) => parts_of_speech.push(val),
-                    None => continue,
-                },
             }
+            "prop" => typically_capitalized = true,
+            val => match PartOfSpeech::from_str(val) {
+                Some(val) => parts_of_speech.push(val),
+                None => continue,
+            },
         }
     }
 
-    let pos;
-    if parts_of_speech.len() > 0 {
-        pos = Some(parts_of_speech);
+    let parts_of_speech = if !parts_of_speech.is_empty() {
+        Some(parts_of_speech)
     } else {
-        pos = None;
-    }
-    let parts_of_speech = pos;
+        None
+    };
 
     let mut definitions = None;
     if let Some(defs) = word_obj.defs {
-        if defs.len() > 0 {
+        if !defs.is_empty() {
             let mut def_list: Vec<Definition> = Vec::new();
 
             for def in defs {
-                let parts: Vec<&str> = def.split('\t').collect();
+                let parts: Vec<&str> = def.splitn(2, '\t').collect(); //The definition itself can contain tabs
 
                 if parts.len() == 2 {
-                    let pos = PartOfSpeech::from_str(&parts[0]);
+                    let pos = PartOfSpeech::from_str(parts[0]);
                     def_list.push(Definition {
                         part_of_speech: pos,
+                        part_of_speech_code: parts[0].to_string(),
                         definition: parts[1].to_string(),
                     });
                 }
@@ -181,22 +672,30 @@ fn word_obj_to_word_elem(word_obj: DatamuseWordObject) -> WordElement {
         score,
         num_syllables,
         parts_of_speech,
-        pronunciation,
+        pronunciation_arpabet,
+        pronunciation_ipa,
         frequency,
+        frequency_raw,
         definitions,
+        definition_headword,
+        typically_capitalized,
+        raw_tags,
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::DatamuseWordObject;
-    use crate::{Definition, PartOfSpeech, WordElement};
+    use super::{
+        DatamuseWordObject, Phoneme, Response, Snapshot, Suggestion, SNAPSHOT_SCHEMA_VERSION,
+    };
+    use crate::{Definition, Error, PartOfSpeech, WordElement};
+    use serde::Deserialize;
 
     #[test]
     fn word_obj_to_word_elem() {
         let word_obj = DatamuseWordObject {
             word: String::from("cow"),
-            score: 2168,
+            score: Some(2168),
             num_syllables: Some(1),
             tags: Some(vec![
                 String::from("n"),
@@ -207,29 +706,41 @@ mod tests {
                 String::from("n\tmature female of mammals of which the male is called `bull'"),
                 String::from("n\tfemale of domestic cattle"),
             ]),
+            def_headword: None,
         };
 
         let actual = super::word_obj_to_word_elem(word_obj);
 
         let expected = WordElement {
             word: String::from("cow"),
-            score: 2168,
+            score: Some(2168),
             num_syllables: Some(1),
             parts_of_speech: Some(vec![PartOfSpeech::Noun]),
-            pronunciation: Some(String::from("K AW1 ")),
+            pronunciation_arpabet: Some(String::from("K AW1 ")),
+            pronunciation_ipa: None,
             frequency: Some(16.567268),
+            frequency_raw: Some(String::from("16.567268")),
             definitions: Some(vec![
                 Definition {
                     part_of_speech: Some(PartOfSpeech::Noun),
+                    part_of_speech_code: String::from("n"),
                     definition: String::from(
                         "mature female of mammals of which the male is called `bull'",
                     ),
                 },
                 Definition {
                     part_of_speech: Some(PartOfSpeech::Noun),
+                    part_of_speech_code: String::from("n"),
                     definition: String::from("female of domestic cattle"),
                 },
             ]),
+            definition_headword: None,
+            typically_capitalized: false,
+            raw_tags: vec![
+                String::from("n"),
+                String::from("pron:K AW1 "),
+                String::from("f:16.567268"),
+            ],
         };
 
         assert_eq!(expected, actual);
@@ -263,40 +774,704 @@ mod tests {
         ]
         "#;
 
-        let actual = super::parse_response(json).unwrap();
+        let actual = super::parse_response(json, false).unwrap();
 
         let expected1 = WordElement {
             word: String::from("milk"),
-            score: 2168,
+            score: Some(2168),
             num_syllables: Some(1),
             parts_of_speech: None,
-            pronunciation: None,
+            pronunciation_arpabet: None,
+            pronunciation_ipa: None,
             frequency: None,
+            frequency_raw: None,
             definitions: None,
+            definition_headword: None,
+            typically_capitalized: false,
+            raw_tags: Vec::new(),
         };
 
         let expected2 = WordElement {
             word: String::from("cow"),
-            score: 2168,
+            score: Some(2168),
             num_syllables: Some(1),
             parts_of_speech: Some(vec![PartOfSpeech::Noun]),
-            pronunciation: Some(String::from("K AW1 ")),
+            pronunciation_arpabet: Some(String::from("K AW1 ")),
+            pronunciation_ipa: None,
             frequency: Some(16.567268),
+            frequency_raw: Some(String::from("16.567268")),
             definitions: Some(vec![
                 Definition {
                     part_of_speech: Some(PartOfSpeech::Noun),
+                    part_of_speech_code: String::from("n"),
                     definition: String::from(
                         "mature female of mammals of which the male is called `bull'",
                     ),
                 },
                 Definition {
                     part_of_speech: Some(PartOfSpeech::Noun),
+                    part_of_speech_code: String::from("n"),
                     definition: String::from("female of domestic cattle"),
                 },
             ]),
+            definition_headword: None,
+            typically_capitalized: false,
+            raw_tags: vec![
+                String::from("n"),
+                String::from("pron:K AW1 "),
+                String::from("f:16.567268"),
+            ],
         };
 
         assert_eq!(expected1, actual[0]);
         assert_eq!(expected2, actual[1]);
     }
+
+    #[test]
+    fn empty_words_removed() {
+        let json = r#"
+        [
+            {"word":"","score":300},
+            {"word":"  ","score":200},
+            {"word":"cow","score":100}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(1, actual.len());
+        assert_eq!("cow", actual[0].word);
+    }
+
+    #[test]
+    fn empty_words_kept() {
+        let json = r#"[{"word":"","score":300},{"word":"cow","score":100}]"#;
+
+        let actual = super::parse_response(json, true).unwrap();
+
+        assert_eq!(2, actual.len());
+        assert_eq!("", actual[0].word);
+    }
+
+    #[test]
+    fn float_num_syllables() {
+        let json = r#"
+        [
+            {"word":"cow","score":300,"numSyllables":1.0},
+            {"word":"cattle","score":200,"numSyllables":2},
+            {"word":"milk","score":100}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some(1), actual[0].num_syllables);
+        assert_eq!(Some(2), actual[1].num_syllables);
+        assert_eq!(None, actual[2].num_syllables);
+    }
+
+    #[test]
+    fn arpabet_syllables() {
+        let json = r#"
+        [
+            {"word":"watermelon","score":300,"tags":["pron:W AO1 T ER0 M EH2 L AH0 N "]},
+            {"word":"winter","score":200,"tags":["pron:W IH1 N T ER0 "]},
+            {"word":"cow","score":100,"tags":["ipa_pron:kaʊ"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(
+            Some(vec![
+                vec![String::from("W"), String::from("AO1")],
+                vec![String::from("T"), String::from("ER0")],
+                vec![String::from("M"), String::from("EH2")],
+                vec![String::from("L"), String::from("AH0"), String::from("N")],
+            ]),
+            actual[0].arpabet_syllables()
+        );
+        assert_eq!(
+            Some(vec![
+                vec![String::from("W"), String::from("IH1"), String::from("N")],
+                vec![String::from("T"), String::from("ER0")],
+            ]),
+            actual[1].arpabet_syllables()
+        );
+        assert_eq!(None, actual[2].arpabet_syllables());
+    }
+
+    #[test]
+    fn is_common() {
+        let json = r#"
+        [
+            {"word":"water","score":300,"tags":["f:212.4"]},
+            {"word":"aqueous","score":200,"tags":["f:0.61"]},
+            {"word":"h2o","score":100}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some(true), actual[0].is_common(10.0));
+        assert_eq!(Some(false), actual[1].is_common(10.0));
+        assert_eq!(None, actual[2].is_common(10.0));
+    }
+
+    #[test]
+    fn unknown_definition_part_of_speech() {
+        let json = r#"
+        [
+            {"word":"hello","score":100,"defs":["u\tan expression of greeting","n\ta greeting"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+        let definitions = actual[0].definitions.as_ref().unwrap();
+
+        assert_eq!(2, definitions.len());
+        assert_eq!(Some(PartOfSpeech::Unknown), definitions[0].part_of_speech);
+        assert_eq!("u", definitions[0].part_of_speech_code);
+        assert_eq!("an expression of greeting", definitions[0].definition);
+        assert_eq!(Some(PartOfSpeech::Noun), definitions[1].part_of_speech);
+        assert_eq!("n", definitions[1].part_of_speech_code);
+    }
+
+    #[test]
+    fn missing_score() {
+        let json = r#"
+        [
+            {"word":"hello world","score":0},
+            {"word":"hello there"}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some(0), actual[0].score);
+        assert_eq!(None, actual[1].score);
+    }
+
+    #[test]
+    fn definition_with_tab() {
+        let json = r#"
+        [
+            {"word":"table","score":100,"defs":["n\ta piece of furniture\t(with legs)"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+        let definitions = actual[0].definitions.as_ref().unwrap();
+
+        assert_eq!(1, definitions.len());
+        assert_eq!(Some(PartOfSpeech::Noun), definitions[0].part_of_speech);
+        assert_eq!(
+            "a piece of furniture\t(with legs)",
+            definitions[0].definition
+        );
+    }
+
+    #[test]
+    fn gloss_and_examples() {
+        let definition = Definition {
+            part_of_speech: Some(PartOfSpeech::Verb),
+            part_of_speech_code: String::from("v"),
+            definition: String::from(
+                r#"move fast by using one's feet; "Don't run; you'll fall"; "He ran to the store""#,
+            ),
+        };
+
+        assert_eq!("move fast by using one's feet", definition.gloss());
+        assert_eq!(
+            vec![
+                String::from("Don't run; you'll fall"),
+                String::from("He ran to the store"),
+            ],
+            definition.examples()
+        );
+
+        let definition = Definition {
+            part_of_speech: Some(PartOfSpeech::Noun),
+            part_of_speech_code: String::from("n"),
+            definition: String::from("female of domestic cattle"),
+        };
+
+        assert_eq!("female of domestic cattle", definition.gloss());
+        assert!(definition.examples().is_empty());
+    }
+
+    #[test]
+    fn typically_capitalized() {
+        let json = r#"
+        [
+            {"word":"paris","score":300,"tags":["n","prop"]},
+            {"word":"London","score":200,"tags":["n"]},
+            {"word":"city","score":100,"tags":["n"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert!(actual[0].typically_capitalized);
+        assert!(actual[1].typically_capitalized);
+        assert!(!actual[2].typically_capitalized);
+    }
+
+    #[test]
+    fn parse_into() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct ScoredWord {
+            word: String,
+            score: u32,
+        }
+
+        let response = Response::new(String::from(
+            r#"[{"word":"cow","score":2168,"tags":["n"]},{"word":"cattle","score":1000}]"#,
+        ));
+
+        let actual: Vec<ScoredWord> = response.parse_into().unwrap();
+
+        assert_eq!(
+            vec![
+                ScoredWord {
+                    word: String::from("cow"),
+                    score: 2168
+                },
+                ScoredWord {
+                    word: String::from("cattle"),
+                    score: 1000
+                }
+            ],
+            actual
+        );
+        assert!(response.parse_into::<Vec<u32>>().is_err());
+    }
+
+    #[test]
+    fn byte_order_mark() {
+        let bytes = b"\xEF\xBB\xBF[{\"word\":\"cow\",\"score\":2168}]".to_vec();
+        let json = String::from_utf8(bytes).unwrap();
+
+        let actual = super::parse_response(&json, false).unwrap();
+
+        assert_eq!(1, actual.len());
+        assert_eq!("cow", actual[0].word);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        let json = r#"
+        [
+            {
+                "word":"cow",
+                "score":2168,
+                "numSyllables":1,
+                "tags":["n","pron:K AW1 ","f:16.567268"],
+                "defs":["n\tfemale of domestic cattle"]
+            }
+        ]
+        "#;
+        let word_list = super::parse_response(json, false).unwrap();
+
+        let serialized = serde_json::to_string(&word_list).unwrap();
+        let deserialized: Vec<WordElement> = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(word_list, deserialized);
+        assert_eq!(
+            "\"adjective\"",
+            serde_json::to_string(&PartOfSpeech::Adjective).unwrap()
+        );
+        assert!(serialized.contains(r#""parts_of_speech":["noun"]"#));
+    }
+
+    #[test]
+    fn clone_word_element() {
+        let json = r#"
+        [
+            {
+                "word":"Lead",
+                "score":300,
+                "numSyllables":1,
+                "tags":["n","v","pron:L IY1 D ","f:10.5"],
+                "defs":["n\ta soft heavy metal","v\tto take somebody somewhere"]
+            }
+        ]
+        "#;
+        let word_list = super::parse_response(json, false).unwrap();
+        let original = &word_list[0];
+
+        let mut cloned = original.clone();
+
+        assert_eq!(*original, cloned);
+        cloned.definitions.as_mut().unwrap().clear();
+        assert_eq!(2, original.definitions.as_ref().unwrap().len());
+        assert_ne!(*original, cloned);
+    }
+
+    #[test]
+    fn primary_pos() {
+        let json = r#"
+        [
+            {"word":"run","score":300,"tags":["v","n"]},
+            {"word":"fast","score":200,"tags":["adj","adv","v"]},
+            {"word":"quick","score":100}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some(PartOfSpeech::Verb), actual[0].primary_pos());
+        assert_eq!(Some(PartOfSpeech::Adjective), actual[1].primary_pos());
+        assert_eq!(None, actual[2].primary_pos());
+    }
+
+    #[test]
+    fn snapshot_round_trip() {
+        let response = Response::new(String::from(
+            r#"[{"word":"milk","score":300,"tags":["n"]},{"word":"cattle","score":200}]"#,
+        ))
+        .with_url(String::from("https://api.datamuse.com/words?ml=cow"));
+
+        let snapshot = response.snapshot().unwrap();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored: Snapshot = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(snapshot, restored);
+        assert_eq!(SNAPSHOT_SCHEMA_VERSION, restored.schema_version);
+        assert_eq!(
+            Some("https://api.datamuse.com/words?ml=cow"),
+            restored.url.as_deref()
+        );
+        assert!(restored.timestamp > 0);
+        assert_eq!(response.list().unwrap(), restored.words);
+        assert_eq!(restored.words, restored.replay().unwrap());
+    }
+
+    #[test]
+    fn raw_tags() {
+        let json = r#"
+        [
+            {"word":"paris","score":100,"tags":["n","prop","query:paris","defHeadword:Paris"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(
+            vec!["n", "prop", "query:paris", "defHeadword:Paris"],
+            actual[0].raw_tags
+        );
+        assert_eq!(Some(vec![PartOfSpeech::Noun]), actual[0].parts_of_speech);
+    }
+
+    #[test]
+    fn unknown_part_of_speech() {
+        let json = r#"
+        [
+            {"word":"hello","score":200,"tags":["u"]},
+            {"word":"world","score":100,"tags":["f:20.5"]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some(vec![PartOfSpeech::Unknown]), actual[0].parts_of_speech);
+        assert_eq!(None, actual[1].parts_of_speech);
+    }
+
+    #[test]
+    fn part_of_speech_checks() {
+        let json = r#"
+        [
+            {"word":"fast","score":300,"tags":["adj","adv","v","n"]},
+            {"word":"quickly","score":200,"tags":["adv"]},
+            {"word":"swift","score":100}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert!(actual[0].is_noun());
+        assert!(actual[0].is_verb());
+        assert!(actual[0].is_adjective());
+        assert!(actual[0].is_adverb());
+        assert!(!actual[0].has_part_of_speech(PartOfSpeech::Unknown));
+
+        assert!(!actual[1].is_noun());
+        assert!(!actual[1].is_verb());
+        assert!(!actual[1].is_adjective());
+        assert!(actual[1].is_adverb());
+
+        assert!(!actual[2].is_noun());
+        assert!(!actual[2].is_verb());
+        assert!(!actual[2].is_adjective());
+        assert!(!actual[2].is_adverb());
+    }
+
+    #[test]
+    fn list_filtered() {
+        let response = Response::new(String::from(
+            r#"[
+                {"word":"run","score":500,"tags":["v","n"]},
+                {"word":"sprint","score":400,"tags":["v"]},
+                {"word":"race","score":300,"tags":["n"]},
+                {"word":"jog","score":200}
+            ]"#,
+        ));
+
+        let nouns = response.list_filtered(PartOfSpeech::Noun).unwrap();
+        let words: Vec<&str> = nouns.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["run", "race"], words);
+
+        let verbs = response.list_filtered(PartOfSpeech::Verb).unwrap();
+        let words: Vec<&str> = verbs.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["run", "sprint"], words);
+
+        assert!(response
+            .list_filtered(PartOfSpeech::Adverb)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn api_error() {
+        let json = r#"{"message":"Invalid value for parameter max"}"#;
+        match super::parse_response(json, false) {
+            Err(Error::ApiError(message)) => assert_eq!("Invalid value for parameter max", message),
+            val => panic!("Expected ApiError, got {:?}", val),
+        }
+
+        match super::parse_response(r#"{"error":"unknown"}"#, false) {
+            Err(Error::SerdeError(_)) => (),
+            val => panic!("Expected SerdeError, got {:?}", val),
+        }
+    }
+
+    #[test]
+    fn best() {
+        let response = Response::new(String::from(
+            r#"[{"word":"hello world","score":500},{"word":"hello","score":400}]"#,
+        ));
+        assert_eq!("hello world", response.best().unwrap().unwrap().word);
+
+        let response = Response::new(String::from("[]"));
+        assert_eq!(None, response.best().unwrap());
+    }
+
+    #[test]
+    fn arpabet_phonemes() {
+        let json = r#"
+        [
+            {"word":"cow","score":300,"tags":["pron:K AW1 "]},
+            {"word":"banana","score":200,"tags":["pron:B AH0 N AE1 N AH0 "]},
+            {"word":"cat","score":100,"tags":["ipa_pron:kæt"]}
+        ]
+        "#;
+        let phoneme = |symbol: &str, stress: Option<u8>| Phoneme {
+            symbol: String::from(symbol),
+            stress,
+        };
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(
+            Some(vec![phoneme("K", None), phoneme("AW", Some(1))]),
+            actual[0].arpabet_phonemes()
+        );
+        assert_eq!(
+            Some(vec![
+                phoneme("B", None),
+                phoneme("AH", Some(0)),
+                phoneme("N", None),
+                phoneme("AE", Some(1)),
+                phoneme("N", None),
+                phoneme("AH", Some(0)),
+            ]),
+            actual[1].arpabet_phonemes()
+        );
+        assert_eq!(None, actual[2].arpabet_phonemes());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn arpabet_and_ipa_pronunciation() {
+        let json = r#"
+        [
+            {"word":"cow","score":200,"tags":["pron:K AW1 ","ipa_pron:kaʊ"]},
+            {"word":"milk","score":100,"tags":["pron:M IH1 L K "]}
+        ]
+        "#;
+
+        let actual = super::parse_response(json, false).unwrap();
+
+        assert_eq!(Some("K AW1 "), actual[0].pronunciation_arpabet.as_deref());
+        assert_eq!(Some("kaʊ"), actual[0].pronunciation_ipa.as_deref());
+        assert_eq!(Some("kaʊ"), actual[0].pronunciation());
+        assert_eq!(2, actual[0].arpabet_phonemes().unwrap().len());
+
+        assert_eq!(None, actual[1].pronunciation_ipa);
+        assert_eq!(Some("M IH1 L K "), actual[1].pronunciation());
+    }
+
+    #[test]
+    fn len() {
+        let json = r#"[
+            {"word":"cow","score":100,"tags":["n","f:1.5"]},
+            {"word":" ","score":90},
+            {"word":"milk","score":80}
+        ]"#;
+        let response = Response::new(String::from(json));
+        assert_eq!(response.list().unwrap().len(), response.len().unwrap());
+        assert_eq!(2, response.len().unwrap());
+        assert!(!response.is_empty().unwrap());
+
+        let response = response.keep_empty_words(true);
+        assert_eq!(response.list().unwrap().len(), response.len().unwrap());
+
+        let response = Response::new(String::from("[]"));
+        assert!(response.is_empty().unwrap());
+
+        let response = Response::new(String::from(r#"{"message":"bad request"}"#));
+        assert!(matches!(response.len(), Err(Error::ApiError(_))));
+    }
+
+    #[test]
+    fn display_word_element() {
+        let mut elem = WordElement {
+            word: String::from("cow"),
+            score: Some(2168),
+            num_syllables: None,
+            parts_of_speech: None,
+            pronunciation_arpabet: None,
+            pronunciation_ipa: None,
+            frequency: None,
+            frequency_raw: None,
+            definitions: None,
+            definition_headword: None,
+            typically_capitalized: false,
+            raw_tags: Vec::new(),
+        };
+        assert_eq!("cow (score: 2168)", elem.to_string());
+
+        elem.num_syllables = Some(1);
+        elem.definitions = Some(vec![Definition {
+            part_of_speech: Some(PartOfSpeech::Noun),
+            part_of_speech_code: String::from("n"),
+            definition: String::from("mature female of mammals; \"the cow was milked\""),
+        }]);
+        assert_eq!(
+            "cow (score: 2168, syllables: 1) - mature female of mammals",
+            elem.to_string()
+        );
+
+        elem.score = None;
+        elem.num_syllables = None;
+        elem.definitions = None;
+        assert_eq!("cow", elem.to_string());
+    }
+
+    #[test]
+    fn iter() {
+        let json = r#"[{"word":"cow","score":100},{"word":"milk","score":80}]"#;
+        let response = Response::new(String::from(json));
+
+        let words: Vec<WordElement> = response.iter().unwrap().collect();
+        assert_eq!(response.list().unwrap(), words);
+
+        let response = Response::new(String::from("{"));
+        assert!(response.iter().is_err());
+    }
+
+    #[test]
+    fn high_precision_frequency() {
+        let json = r#"[{"word":"cow","score":100,"tags":["f:16.5672681234567"]},
+            {"word":"moo","score":90,"tags":["f:unknown"]}]"#;
+        let word_list = Response::new(String::from(json)).list().unwrap();
+
+        assert_eq!(
+            Some("16.5672681234567"),
+            word_list[0].frequency_raw.as_deref()
+        );
+        assert_eq!(
+            word_list[0]
+                .frequency_raw
+                .as_ref()
+                .unwrap()
+                .parse::<f64>()
+                .ok(),
+            word_list[0].frequency
+        );
+        assert_eq!(Some(16.5672681234567), word_list[0].frequency);
+
+        assert_eq!(None, word_list[1].frequency);
+        assert_eq!(Some("unknown"), word_list[1].frequency_raw.as_deref());
+    }
+
+    #[test]
+    fn from_json() {
+        let json = r#"[{"word":"cow","score":2168,"numSyllables":1,"tags":["n","f:16.567268"]}]"#;
+
+        let word_list = Response::from_json(String::from(json)).list().unwrap();
+        assert_eq!(1, word_list.len());
+        assert_eq!("cow", word_list[0].word);
+        assert_eq!(Some(2168), word_list[0].score);
+        assert_eq!(Some(1), word_list[0].num_syllables);
+        assert_eq!(Some(16.567268), word_list[0].frequency);
+    }
+
+    #[test]
+    fn definition_headword() {
+        let json = r#"[
+            {"word":"ran","score":100,"tags":["v","defHeadword:run"],"defs":["v\tmove fast"]},
+            {"word":"went","score":90,"defs":["v\tchange location"],"defHeadword":"go"},
+            {"word":"walk","score":80,"defs":["v\tuse one's feet"]}
+        ]"#;
+        let word_list = Response::from_json(String::from(json)).list().unwrap();
+
+        assert_eq!(Some("run"), word_list[0].definition_headword.as_deref());
+        assert_eq!(Some("go"), word_list[1].definition_headword.as_deref());
+        assert_eq!(None, word_list[2].definition_headword);
+    }
+
+    #[test]
+    fn words() {
+        let json = r#"[{"word":"cow","score":100},{"word":" ","score":90},{"word":"milk"}]"#;
+        let response = Response::from_json(String::from(json));
+
+        let expected: Vec<String> = response
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|elem| elem.word)
+            .collect();
+        assert_eq!(expected, response.words().unwrap());
+        assert_eq!(vec!["cow", "milk"], response.words().unwrap());
+    }
+
+    #[test]
+    fn suggestions() {
+        let json =
+            r#"[{"word":"hello","score":2356},{"word":"help","score":2232},{"word":"hell"}]"#;
+        let suggestions = Response::from_json(String::from(json))
+            .suggestions()
+            .unwrap();
+
+        assert_eq!(
+            vec![
+                Suggestion {
+                    word: String::from("hello"),
+                    rank: 1
+                },
+                Suggestion {
+                    word: String::from("help"),
+                    rank: 2
+                },
+                Suggestion {
+                    word: String::from("hell"),
+                    rank: 3
+                },
+            ],
+            suggestions
+        );
+    }
 }
@@ -0,0 +1,179 @@
+//A minimal http server used by the tests to mock the Datamuse api
+use crate::response::{Response, WordElement};
+use crate::DatamuseClient;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[derive(Clone, Debug)]
+pub(crate) struct MockResponse {
+    status: u16,
+    body: Vec<u8>,
+    delay: Option<Duration>,
+    gzip: bool,
+}
+
+#[derive(Debug)]
+pub(crate) struct MockServer {
+    url: String,
+    hits: Arc<AtomicUsize>,
+    last_request: Arc<Mutex<String>>,
+}
+
+impl MockResponse {
+    pub(crate) fn json(body: &str) -> Self {
+        MockResponse {
+            status: 200,
+            body: body.as_bytes().to_vec(),
+            delay: None,
+            gzip: false,
+        }
+    }
+
+    pub(crate) fn status(status: u16, body: &str) -> Self {
+        MockResponse {
+            status,
+            body: body.as_bytes().to_vec(),
+            delay: None,
+            gzip: false,
+        }
+    }
+
+    //The body is sent gzip compressed with the matching Content-Encoding header
+    pub(crate) fn gzip(body: &str) -> Self {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body.as_bytes()).unwrap();
+
+        MockResponse {
+            status: 200,
+            body: encoder.finish().unwrap(),
+            delay: None,
+            gzip: true,
+        }
+    }
+
+    //The response is only sent after waiting for the given duration
+    pub(crate) fn delayed(mut self, delay: Duration) -> Self {
+        self.delay = Some(delay);
+
+        self
+    }
+}
+
+impl MockServer {
+    //Responses are returned in order, the last one is repeated for any further requests
+    pub(crate) async fn start(responses: Vec<MockResponse>) -> Self {
+        MockServer::serve(Vec::new(), responses).await
+    }
+
+    //Each response is returned for requests whose request line contains the given pattern
+    pub(crate) async fn routes(routes: Vec<(&str, MockResponse)>) -> Self {
+        let routes = routes
+            .into_iter()
+            .map(|(pattern, response)| (String::from(pattern), response))
+            .collect();
+        MockServer::serve(routes, vec![MockResponse::json("[]")]).await
+    }
+
+    //A client which sends all of its requests to this server
+    pub(crate) fn client(&self) -> DatamuseClient {
+        DatamuseClient::new()
+            .with_base_url(&self.url)
+            .require_https(false)
+    }
+
+    async fn serve(routes: Vec<(String, MockResponse)>, responses: Vec<MockResponse>) -> Self {
+        let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let last_request = Arc::new(Mutex::new(String::new()));
+
+        let server_hits = hits.clone();
+        let server_last_request = last_request.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = match listener.accept().await {
+                    Ok(val) => val,
+                    Err(_) => return,
+                };
+
+                let request = read_request(&mut stream).await;
+                let request_line = request.lines().next().unwrap_or("");
+                let index = server_hits.fetch_add(1, Ordering::SeqCst);
+                *server_last_request.lock().unwrap() = request.clone();
+
+                let response = match routes
+                    .iter()
+                    .find(|(pattern, _)| request_line.contains(pattern.as_str()))
+                {
+                    Some((_, response)) => response,
+                    None => &responses[index.min(responses.len() - 1)],
+                };
+                if let Some(delay) = response.delay {
+                    tokio::time::delay_for(delay).await;
+                }
+                let _ = stream.write_all(&response.to_bytes()).await;
+                let _ = stream.shutdown(std::net::Shutdown::Write);
+            }
+        });
+
+        MockServer {
+            url,
+            hits,
+            last_request,
+        }
+    }
+
+    pub(crate) fn hits(&self) -> usize {
+        self.hits.load(Ordering::SeqCst)
+    }
+
+    //The request line and headers of the most recent request
+    pub(crate) fn last_request(&self) -> String {
+        self.last_request.lock().unwrap().clone()
+    }
+}
+
+impl MockResponse {
+    fn to_bytes(&self) -> Vec<u8> {
+        let encoding = if self.gzip {
+            "Content-Encoding: gzip\r\n"
+        } else {
+            ""
+        };
+        let head = format!(
+            "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\n{}Connection: close\r\n\r\n",
+            self.status,
+            self.body.len(),
+            encoding
+        );
+
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+async fn read_request(stream: &mut tokio::net::TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        match stream.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(len) => request.extend_from_slice(&buffer[..len]),
+        }
+    }
+
+    String::from_utf8_lossy(&request).into_owned()
+}
+
+//Parses a word list from json as if it had been returned by the api
+pub(crate) fn fixture(json: &str) -> Vec<WordElement> {
+    Response::new(String::from(json)).list().unwrap()
+}
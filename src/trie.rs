@@ -0,0 +1,141 @@
+use crate::request::RequestBuilder;
+use crate::response::WordElement;
+use crate::Result;
+use std::collections::BTreeMap;
+
+/// A prefix tree of words which can be used as a local index for autocompletion.
+/// It can be built from any word list, for example the results of a broad spelled_like
+/// query such as "a*", using [from_words()](WordTrie::from_words) or directly from a query
+/// using [trie()](crate::RequestBuilder::trie)
+#[derive(Clone, Debug, Default)]
+pub struct WordTrie {
+    root: TrieNode,
+    len: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct TrieNode {
+    children: BTreeMap<char, TrieNode>,
+    word: Option<String>,
+}
+
+impl WordTrie {
+    /// Returns a new, empty WordTrie
+    pub fn new() -> Self {
+        WordTrie::default()
+    }
+
+    /// Returns a new WordTrie containing the words of all of the given elements
+    pub fn from_words(words: &[WordElement]) -> Self {
+        let mut trie = WordTrie::new();
+        for elem in words {
+            trie.insert(&elem.word);
+        }
+
+        trie
+    }
+
+    /// Adds a word to the trie. Adding a word which is already present has no effect
+    pub fn insert(&mut self, word: &str) {
+        let mut node = &mut self.root;
+        for c in word.chars() {
+            node = node.children.entry(c).or_default();
+        }
+
+        if node.word.is_none() {
+            node.word = Some(String::from(word));
+            self.len += 1;
+        }
+    }
+
+    /// Returns all words in the trie which start with the given prefix in alphabetical order
+    pub fn completions(&self, prefix: &str) -> Vec<&str> {
+        let mut node = &self.root;
+        for c in prefix.chars() {
+            node = match node.children.get(&c) {
+                Some(child) => child,
+                None => return Vec::new(),
+            };
+        }
+
+        let mut completions = Vec::new();
+        node.collect_words(&mut completions);
+
+        completions
+    }
+
+    /// Returns whether the given word is in the trie
+    pub fn contains(&self, word: &str) -> bool {
+        let mut node = &self.root;
+        for c in word.chars() {
+            node = match node.children.get(&c) {
+                Some(child) => child,
+                None => return false,
+            };
+        }
+
+        node.word.is_some()
+    }
+
+    /// Returns the number of words in the trie
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the trie contains no words
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl TrieNode {
+    fn collect_words<'a>(&'a self, words: &mut Vec<&'a str>) {
+        if let Some(word) = &self.word {
+            words.push(word);
+        }
+
+        for child in self.children.values() {
+            child.collect_words(words);
+        }
+    }
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// A convenience method to send the request and build a [WordTrie](WordTrie) from the
+    /// resulting word list
+    pub async fn trie(&self) -> Result<WordTrie> {
+        Ok(WordTrie::from_words(&self.list().await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WordTrie;
+    use crate::response::Response;
+
+    #[test]
+    fn insert_and_complete() {
+        let words = Response::new(String::from(
+            r#"[
+                {"word":"car","score":500},
+                {"word":"cart","score":400},
+                {"word":"carbon","score":300},
+                {"word":"cat","score":200},
+                {"word":"dog","score":100}
+            ]"#,
+        ))
+        .list()
+        .unwrap();
+
+        let mut trie = WordTrie::from_words(&words);
+        trie.insert("car");
+
+        assert_eq!(5, trie.len());
+        assert_eq!(vec!["car", "carbon", "cart"], trie.completions("car"));
+        assert_eq!(vec!["car", "carbon", "cart", "cat"], trie.completions("ca"));
+        assert_eq!(5, trie.completions("").len());
+        assert!(trie.completions("x").is_empty());
+        assert!(trie.contains("cat"));
+        assert!(!trie.contains("ca"));
+    }
+}
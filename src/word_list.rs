@@ -0,0 +1,564 @@
+use crate::request::RequestBuilder;
+use crate::response::{PartOfSpeech, WordElement};
+use crate::{Error, Result};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// This trait provides helper methods for working with the lists of words returned by the api.
+/// It is implemented for slices of [WordElement](WordElement) and as such can be used directly
+/// on the Vec returned by list()
+pub trait WordList {
+    /// Returns a new list where each word which can be multiple parts of speech is split into one
+    /// element per part of speech (e.g. "lead" as a noun and "lead" as a verb). The data of the
+    /// original element is cloned into each new element, except that parts_of_speech only contains
+    /// the one part of speech and definitions only contains the definitions of that part of speech
+    /// (and those without one). Words with at most one part of speech are cloned unchanged
+    fn split_homographs(&self) -> Vec<WordElement>;
+
+    /// Compares this list against a previous list of the same query and returns which words
+    /// were added and removed as well as which words have a different score. This can be used
+    /// to detect changes in the api's results over time
+    fn diff(&self, previous: &[WordElement]) -> ResultDiff;
+
+    /// Returns the standardized score (z-score) of each word, which is its score minus the mean
+    /// score divided by the standard deviation of the scores of the list. This allows comparing
+    /// words from different queries. Words without a score are left out. If all scores are equal
+    /// (including a single word), every z-score is 0.0
+    fn zscore_map(&self) -> HashMap<String, f32>;
+
+    /// Returns the part of speech found most often among the words, counting every part of
+    /// speech of each word. If several are found equally often, the one which appears first in
+    /// the list is returned. This returns None if no word has parts of speech, which is the case
+    /// unless the meta data flag [PartsOfSpeech](crate::MetaDataFlag::PartsOfSpeech) is set
+    fn dominant_pos(&self) -> Option<PartOfSpeech>;
+
+    /// Returns a copy of the list ordered by a combination of the score, the frequency and the
+    /// brevity (one divided by the number of syllables) of each word, weighted by the given
+    /// [CompositeWeights](CompositeWeights). The score and frequency are divided by their
+    /// highest value in the list, so that all three values are between 0 and 1. Missing values
+    /// count as 0, so the metadata flags [WordFrequency](crate::MetaDataFlag::WordFrequency) and
+    /// [SyllableCount](crate::MetaDataFlag::SyllableCount) should be set if they are weighted.
+    /// Words with the same combined value keep their order
+    fn rank_composite(&self, weights: CompositeWeights) -> Vec<WordElement>;
+}
+
+/// The relative weights of the values combined by [rank_composite()](WordList::rank_composite).
+/// By default only the score is weighted, which keeps the order of the api
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CompositeWeights {
+    /// The weight of the score, i.e. how well the word fits the query
+    pub score: f32,
+    /// The weight of the frequency, i.e. how common the word is
+    pub frequency: f32,
+    /// The weight of the brevity, i.e. how few syllables the word has
+    pub brevity: f32,
+}
+
+/// The differences between two lists of words as returned by [diff()](WordList::diff)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResultDiff {
+    /// The words only found in the current list in the order of the current list
+    pub added: Vec<String>,
+    /// The words only found in the previous list in the order of the previous list
+    pub removed: Vec<String>,
+    /// The words found in both lists but with different scores in the order of the current list
+    pub score_changes: Vec<ScoreChange>,
+}
+
+/// A change of the score of a word between two lists of words
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScoreChange {
+    /// The word whose score changed
+    pub word: String,
+    /// The score of the word in the previous list
+    pub previous: Option<usize>,
+    /// The score of the word in the current list
+    pub current: Option<usize>,
+}
+
+/// A list of words split into pages of equal size (except for the last page) as returned by
+/// [list_paged_model()](RequestBuilder::list_paged_model). This can be used directly as the
+/// model of a paginated user interface
+#[derive(Clone, Debug, PartialEq)]
+pub struct PagedWords {
+    /// All words of the list in the order returned by the api
+    pub words: Vec<WordElement>,
+    /// The total number of words
+    pub total: usize,
+    /// The highest number of words on each page
+    pub page_size: usize,
+    /// The number of pages, which is 0 if there are no words
+    pub num_pages: usize,
+}
+
+impl PagedWords {
+    /// Returns a new PagedWords struct with the given words split into pages of page_size words.
+    /// A page_size of 0 results in no pages
+    pub fn new(words: Vec<WordElement>, page_size: usize) -> Self {
+        let total = words.len();
+        let num_pages = match page_size {
+            0 => 0,
+            size => match total {
+                0 => 0,
+                total => (total - 1) / size + 1,
+            },
+        };
+
+        PagedWords {
+            words,
+            total,
+            page_size,
+            num_pages,
+        }
+    }
+
+    /// Returns the words on the given page, counting from 0, or None if there is no such page
+    pub fn page(&self, n: usize) -> Option<&[WordElement]> {
+        if n >= self.num_pages {
+            return None;
+        }
+
+        let start = n * self.page_size;
+        let end = (start + self.page_size).min(self.total);
+        Some(&self.words[start..end])
+    }
+}
+
+impl Default for CompositeWeights {
+    fn default() -> Self {
+        CompositeWeights {
+            score: 1.0,
+            frequency: 0.0,
+            brevity: 0.0,
+        }
+    }
+}
+
+impl ResultDiff {
+    /// Returns whether there are no differences between the two lists
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.score_changes.is_empty()
+    }
+}
+
+impl WordList for [WordElement] {
+    fn split_homographs(&self) -> Vec<WordElement> {
+        let mut split_list: Vec<WordElement> = Vec::new();
+
+        for elem in self {
+            let parts_of_speech = match &elem.parts_of_speech {
+                Some(parts_of_speech) if parts_of_speech.len() > 1 => parts_of_speech,
+                _ => {
+                    split_list.push(elem.clone());
+                    continue;
+                }
+            };
+
+            for pos in parts_of_speech {
+                let mut split_elem = elem.clone();
+                split_elem.parts_of_speech = Some(vec![*pos]);
+                split_elem.definitions = elem.definitions.as_ref().map(|defs| {
+                    defs.iter()
+                        .filter(|def| {
+                            def.part_of_speech.is_none() || def.part_of_speech == Some(*pos)
+                        })
+                        .cloned()
+                        .collect()
+                });
+
+                split_list.push(split_elem);
+            }
+        }
+
+        split_list
+    }
+
+    fn diff(&self, previous: &[WordElement]) -> ResultDiff {
+        let previous_scores: HashMap<&str, Option<usize>> = previous
+            .iter()
+            .map(|elem| (elem.word.as_str(), elem.score))
+            .collect();
+        let current_scores: HashMap<&str, Option<usize>> = self
+            .iter()
+            .map(|elem| (elem.word.as_str(), elem.score))
+            .collect();
+
+        let mut diff = ResultDiff::default();
+        for elem in self {
+            match previous_scores.get(elem.word.as_str()) {
+                None => diff.added.push(elem.word.clone()),
+                Some(score) if *score != elem.score => diff.score_changes.push(ScoreChange {
+                    word: elem.word.clone(),
+                    previous: *score,
+                    current: elem.score,
+                }),
+                Some(_) => (),
+            }
+        }
+
+        for elem in previous {
+            if !current_scores.contains_key(elem.word.as_str()) {
+                diff.removed.push(elem.word.clone());
+            }
+        }
+
+        diff
+    }
+
+    fn zscore_map(&self) -> HashMap<String, f32> {
+        let scores: Vec<(&str, f64)> = self
+            .iter()
+            .filter_map(|elem| elem.score.map(|score| (elem.word.as_str(), score as f64)))
+            .collect();
+
+        let count = scores.len() as f64;
+        let mean = scores.iter().map(|(_, score)| score).sum::<f64>() / count;
+        let variance = scores
+            .iter()
+            .map(|(_, score)| (score - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let std_dev = variance.sqrt();
+
+        scores
+            .into_iter()
+            .map(|(word, score)| {
+                let zscore = if std_dev > 0.0 {
+                    (score - mean) / std_dev
+                } else {
+                    0.0
+                };
+
+                (String::from(word), zscore as f32)
+            })
+            .collect()
+    }
+
+    fn dominant_pos(&self) -> Option<PartOfSpeech> {
+        let mut counts: Vec<(PartOfSpeech, usize)> = Vec::new(); //Keeps the order of appearance
+
+        for pos in self
+            .iter()
+            .filter_map(|elem| elem.parts_of_speech.as_ref())
+            .flatten()
+        {
+            match counts.iter_mut().find(|(val, _)| val == pos) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((*pos, 1)),
+            }
+        }
+
+        let max = counts.iter().map(|(_, count)| *count).max()?;
+        counts
+            .into_iter()
+            .find(|(_, count)| *count == max)
+            .map(|(pos, _)| pos)
+    }
+
+    fn rank_composite(&self, weights: CompositeWeights) -> Vec<WordElement> {
+        let max_score = self.iter().filter_map(|elem| elem.score).max().unwrap_or(0) as f32;
+        let max_frequency = self
+            .iter()
+            .filter_map(|elem| elem.frequency)
+            .fold(0.0, f64::max);
+
+        let mut ranked: Vec<(f32, &WordElement)> = self
+            .iter()
+            .map(|elem| {
+                let score = match elem.score {
+                    Some(score) if max_score > 0.0 => score as f32 / max_score,
+                    _ => 0.0,
+                };
+                let frequency = match elem.frequency {
+                    Some(frequency) if max_frequency > 0.0 => (frequency / max_frequency) as f32,
+                    _ => 0.0,
+                };
+                let brevity = match elem.num_syllables {
+                    Some(syllables) if syllables > 0 => 1.0 / syllables as f32,
+                    _ => 0.0,
+                };
+
+                let combined = weights.score * score
+                    + weights.frequency * frequency
+                    + weights.brevity * brevity;
+                (combined, elem)
+            })
+            .collect();
+
+        ranked.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        ranked.into_iter().map(|(_, elem)| elem.clone()).collect()
+    }
+}
+
+/// Sorts the words by their frequency from most to least common. Words without a frequency are
+/// placed last and words with equal frequencies keep their order. The frequencies are only
+/// known if the meta data flag [WordFrequency](crate::MetaDataFlag::WordFrequency) was set
+/// for the query
+pub fn sort_by_frequency(words: &mut [WordElement]) {
+    words.sort_by(|a, b| match (a.frequency, b.frequency) {
+        (Some(a), Some(b)) => b.partial_cmp(&a).unwrap_or(Ordering::Equal),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
+
+impl<'a> RequestBuilder<'a> {
+    /// A convenience method to send the request and split the resulting word list into pages of
+    /// the given size, see [PagedWords](PagedWords). Unlike [paged()](RequestBuilder::paged)
+    /// this sends a single request. An [Error::ParameterError](Error::ParameterError) is returned
+    /// if the page size is 0
+    pub async fn list_paged_model(&self, page_size: usize) -> Result<PagedWords> {
+        if page_size == 0 {
+            return Err(Error::ParameterError(String::from(
+                "The page size must be greater than 0",
+            )));
+        }
+
+        Ok(PagedWords::new(self.list().await?, page_size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompositeWeights, PagedWords, ResultDiff, ScoreChange, WordList};
+    use crate::test_server::{fixture, MockResponse, MockServer};
+    use crate::{EndPoint, Error, PartOfSpeech, Vocabulary};
+
+    #[test]
+    fn split_homographs() {
+        let word_list = fixture(
+            r#"[
+                {
+                    "word":"lead",
+                    "score":300,
+                    "tags":["n","v","f:10.5"],
+                    "defs":["n\ta soft heavy metal","v\tto take somebody somewhere"]
+                },
+                {"word":"guide","score":200,"tags":["v"]}
+            ]"#,
+        );
+
+        let actual = word_list.split_homographs();
+
+        assert_eq!(3, actual.len());
+        assert_eq!("lead", actual[0].word);
+        assert_eq!(Some(vec![PartOfSpeech::Noun]), actual[0].parts_of_speech);
+        assert_eq!(Some(10.5), actual[0].frequency);
+        let noun_defs = actual[0].definitions.as_ref().unwrap();
+        assert_eq!(1, noun_defs.len());
+        assert_eq!("a soft heavy metal", noun_defs[0].definition);
+
+        assert_eq!("lead", actual[1].word);
+        assert_eq!(Some(vec![PartOfSpeech::Verb]), actual[1].parts_of_speech);
+        assert_eq!(Some(10.5), actual[1].frequency);
+        let verb_defs = actual[1].definitions.as_ref().unwrap();
+        assert_eq!(1, verb_defs.len());
+        assert_eq!("to take somebody somewhere", verb_defs[0].definition);
+
+        assert_eq!(word_list[1], actual[2]);
+    }
+
+    #[test]
+    fn diff() {
+        let previous = fixture(
+            r#"[
+                {"word":"hat","score":300},
+                {"word":"bat","score":200},
+                {"word":"mat","score":100}
+            ]"#,
+        );
+        let current = fixture(
+            r#"[
+                {"word":"hat","score":300},
+                {"word":"sat","score":250},
+                {"word":"bat","score":150}
+            ]"#,
+        );
+
+        let expected = ResultDiff {
+            added: vec![String::from("sat")],
+            removed: vec![String::from("mat")],
+            score_changes: vec![ScoreChange {
+                word: String::from("bat"),
+                previous: Some(200),
+                current: Some(150),
+            }],
+        };
+
+        assert_eq!(expected, current.diff(&previous));
+        assert!(current.diff(&current).is_empty());
+    }
+
+    #[test]
+    fn zscore_map() {
+        let word_list = fixture(
+            r#"[
+                {"word":"a","score":10},
+                {"word":"b","score":20},
+                {"word":"c","score":30},
+                {"word":"d"}
+            ]"#,
+        );
+
+        let actual = word_list.zscore_map();
+
+        //mean 20, standard deviation sqrt(200 / 3)
+        assert_eq!(3, actual.len());
+        assert!((actual["a"] + 1.224_745).abs() < 1e-5);
+        assert!(actual["b"].abs() < 1e-5);
+        assert!((actual["c"] - 1.224_745).abs() < 1e-5);
+    }
+
+    #[test]
+    fn zscore_map_no_variance() {
+        let single = fixture(r#"[{"word":"a","score":10}]"#);
+        assert_eq!(0.0, single.zscore_map()["a"]);
+
+        let equal = fixture(r#"[{"word":"a","score":10},{"word":"b","score":10}]"#);
+        assert_eq!(0.0, equal.zscore_map()["a"]);
+        assert_eq!(0.0, equal.zscore_map()["b"]);
+
+        assert!(fixture("[]").zscore_map().is_empty());
+    }
+
+    #[test]
+    fn dominant_pos() {
+        let word_list = fixture(
+            r#"[
+                {"word":"run","score":500,"tags":["n","v"]},
+                {"word":"sprint","score":400,"tags":["v"]},
+                {"word":"dash","score":300,"tags":["v","n"]},
+                {"word":"race","score":200,"tags":["n"]},
+                {"word":"hurry","score":100,"tags":["v"]},
+                {"word":"jog","score":50}
+            ]"#,
+        );
+
+        assert_eq!(Some(PartOfSpeech::Verb), word_list.dominant_pos());
+        assert_eq!(Some(PartOfSpeech::Noun), word_list[..1].dominant_pos());
+        assert_eq!(None, word_list[5..].dominant_pos());
+    }
+
+    #[tokio::test]
+    async fn list_paged_model() {
+        let server = MockServer::start(vec![MockResponse::json(
+            r#"[
+                {"word":"a","score":700},
+                {"word":"b","score":600},
+                {"word":"c","score":500},
+                {"word":"d","score":400},
+                {"word":"e","score":300},
+                {"word":"f","score":200},
+                {"word":"g","score":100}
+            ]"#,
+        )])
+        .await;
+        let client = server.client();
+        let request = client
+            .new_query(Vocabulary::English, EndPoint::Words)
+            .means_like("letter");
+
+        let paged = request.list_paged_model(3).await.unwrap();
+
+        assert_eq!(7, paged.total);
+        assert_eq!(3, paged.page_size);
+        assert_eq!(3, paged.num_pages);
+        let pages: Vec<Vec<&str>> = (0..paged.num_pages)
+            .map(|n| {
+                paged
+                    .page(n)
+                    .unwrap()
+                    .iter()
+                    .map(|elem| elem.word.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec!["a", "b", "c"], vec!["d", "e", "f"], vec!["g"]],
+            pages
+        );
+        assert_eq!(None, paged.page(3));
+
+        assert_eq!(1, request.list_paged_model(7).await.unwrap().num_pages);
+        match request.list_paged_model(0).await {
+            Err(Error::ParameterError(_)) => (),
+            val => panic!("Expected ParameterError, got {:?}", val),
+        }
+    }
+
+    #[test]
+    fn paged_words_empty() {
+        let paged = PagedWords::new(Vec::new(), 10);
+
+        assert_eq!(0, paged.total);
+        assert_eq!(0, paged.num_pages);
+        assert_eq!(None, paged.page(0));
+    }
+
+    #[test]
+    fn rank_composite() {
+        let word_list = fixture(
+            r#"[
+                {"word":"automobile","score":300,"numSyllables":4,"tags":["f:5.0"]},
+                {"word":"vehicle","score":200,"numSyllables":3,"tags":["f:40.0"]},
+                {"word":"car","score":100,"numSyllables":1,"tags":["f:20.0"]}
+            ]"#,
+        );
+        let order = |weights: CompositeWeights| -> Vec<String> {
+            word_list
+                .rank_composite(weights)
+                .into_iter()
+                .map(|elem| elem.word)
+                .collect()
+        };
+
+        assert_eq!(
+            vec!["automobile", "vehicle", "car"],
+            order(CompositeWeights::default())
+        );
+        assert_eq!(
+            vec!["vehicle", "car", "automobile"],
+            order(CompositeWeights {
+                score: 0.0,
+                frequency: 1.0,
+                brevity: 0.0
+            })
+        );
+        assert_eq!(
+            vec!["car", "vehicle", "automobile"],
+            order(CompositeWeights {
+                score: 0.0,
+                frequency: 0.0,
+                brevity: 1.0
+            })
+        );
+        //automobile 1.0 + 0.125, vehicle 0.667 + 1.0, car 0.333 + 0.5
+        assert_eq!(
+            vec!["vehicle", "automobile", "car"],
+            order(CompositeWeights {
+                score: 1.0,
+                frequency: 1.0,
+                brevity: 0.0
+            })
+        );
+    }
+
+    #[test]
+    fn sort_by_frequency() {
+        let mut word_list = fixture(
+            r#"[
+                {"word":"azure","score":500,"tags":["f:2.5"]},
+                {"word":"cerulean","score":400},
+                {"word":"blue","score":300,"tags":["f:120.0"]},
+                {"word":"cobalt","score":200},
+                {"word":"navy","score":100,"tags":["f:9.8"]}
+            ]"#,
+        );
+
+        super::sort_by_frequency(&mut word_list);
+
+        let words: Vec<&str> = word_list.iter().map(|elem| elem.word.as_str()).collect();
+        assert_eq!(vec!["blue", "navy", "azure", "cerulean", "cobalt"], words);
+    }
+}